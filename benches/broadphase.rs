@@ -0,0 +1,70 @@
+//! Times each broadphase on growing numbers of densely packed entities.
+//!
+//! Run with `cargo bench --bench broadphase`.
+
+use simple_physics_engine::{
+    Broadphase, BruteForce, Bvh, Entity, Motion, SweepAndPrune, UniformGrid,
+};
+use std::{cell::RefCell, time::Instant};
+
+const RADIUS: f32 = 5.0;
+const ITERATIONS: u32 = 5;
+const BRUTE_FORCE_LIMIT: usize = 16_000;
+
+fn lattice(count: usize) -> Vec<RefCell<Entity>> {
+    let side = (count as f32).sqrt().ceil() as usize;
+
+    (0..count)
+        .map(|i| {
+            let (x, y) = ((i % side) as f32, (i / side) as f32);
+            let jitter = ((i * 7919) % 13) as f32 * 0.1;
+            let motion = Motion::new(x * RADIUS * 1.9 + jitter, y * RADIUS * 1.9 - jitter);
+            RefCell::new(Entity::new(RADIUS, motion))
+        })
+        .collect()
+}
+
+fn time(broadphase: &mut dyn Broadphase, entities: &[RefCell<Entity>]) -> (f64, usize) {
+    let mut pairs = Vec::new();
+    broadphase.pairs(entities, &mut pairs);
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        broadphase.pairs(entities, &mut pairs);
+    }
+
+    (
+        start.elapsed().as_secs_f64() * 1000.0 / ITERATIONS as f64,
+        pairs.len(),
+    )
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>16} {:>12} {:>12}",
+        "entities", "broadphase", "ms/step", "pairs"
+    );
+
+    for count in [1_000, 4_000, 16_000, 64_000, 256_000] {
+        let entities = lattice(count);
+        let mut broadphases: Vec<Box<dyn Broadphase>> = vec![
+            Box::new(UniformGrid::new()),
+            Box::new(SweepAndPrune::default()),
+            Box::new(Bvh::default()),
+        ];
+        if count <= BRUTE_FORCE_LIMIT {
+            broadphases.push(Box::new(BruteForce));
+        }
+
+        for broadphase in &mut broadphases {
+            let (ms, pairs) = time(broadphase.as_mut(), &entities);
+            println!(
+                "{:>10} {:>16} {:>12.3} {:>12}",
+                count,
+                broadphase.name(),
+                ms,
+                pairs
+            );
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use glam::Vec2;
+
+/// An axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Aabb {
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_circle(center: Vec2, radius: f32) -> Self {
+        Self::new(center - Vec2::splat(radius), center + Vec2::splat(radius))
+    }
+
+    /// Bounds of a capsule: the segment `start`–`end` thickened by `radius`.
+    pub fn from_capsule(start: Vec2, end: Vec2, radius: f32) -> Self {
+        Self::new(start.min(end), start.max(end)).expand(radius)
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn perimeter(&self) -> f32 {
+        let size = self.size();
+        2.0 * (size.x + size.y)
+    }
+
+    pub fn expand(&self, margin: f32) -> Self {
+        Self::new(
+            self.min - Vec2::splat(margin),
+            self.max + Vec2::splat(margin),
+        )
+    }
+
+    pub fn union(&self, other: &Aabb) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    pub fn contains(&self, other: &Aabb) -> bool {
+        self.min.x <= other.min.x
+            && self.min.y <= other.min.y
+            && other.max.x <= self.max.x
+            && other.max.y <= self.max.y
+    }
+
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y
+    }
+}
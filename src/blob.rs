@@ -0,0 +1,116 @@
+use crate::{geometry::signed_area, Entity, Motion, Pressure, Stick, World};
+use glam::Vec2;
+use std::{cell::RefCell, f32::consts::TAU, ops::Range};
+
+/// Builds a soft blob: a ring of particles around `center` joined by sticks,
+/// with a [`Pressure`] keeping its area up so it squashes and springs back
+/// like jelly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Blob {
+    pub center: Vec2,
+    pub radius: f32,
+    /// Number of particles around the ring.
+    pub particles: usize,
+    /// Radius of each particle, by default enough for neighbours to touch so
+    /// balls cannot slip between them.
+    pub particle_radius: f32,
+    /// Stiffness of the sticks around the rim, see [`Stick::stiffness`].
+    pub rim_stiffness: f32,
+    /// Stiffness of the pressure, see [`Pressure::stiffness`].
+    pub pressure_stiffness: f32,
+    /// Target area relative to the area the blob is laid out with. A rigid
+    /// rim already encloses the most area it can, so inflating past `1` only
+    /// grows blobs whose rim can stretch.
+    pub inflation: f32,
+}
+
+impl Blob {
+    pub fn new(center: Vec2, radius: f32, particles: usize) -> Self {
+        assert!(particles >= 3, "a blob needs at least three particles");
+        Self {
+            center,
+            radius,
+            particles,
+            particle_radius: radius * (TAU / particles as f32 / 2.0).sin(),
+            rim_stiffness: f32::INFINITY,
+            pressure_stiffness: f32::INFINITY,
+            inflation: 1.0,
+        }
+    }
+
+    pub fn with_particle_radius(mut self, radius: f32) -> Self {
+        self.particle_radius = radius;
+        self
+    }
+
+    pub fn with_rim_stiffness(mut self, stiffness: f32) -> Self {
+        self.rim_stiffness = stiffness;
+        self
+    }
+
+    pub fn with_pressure_stiffness(mut self, stiffness: f32) -> Self {
+        self.pressure_stiffness = stiffness;
+        self
+    }
+
+    pub fn with_inflation(mut self, inflation: f32) -> Self {
+        self.inflation = inflation;
+        self
+    }
+
+    /// The particles around the ring, the sticks between neighbours and the
+    /// pressure inside, numbered as if the first particle will be stored at
+    /// index `first`. The particles share `first` as their
+    /// [`Entity::group`] and are hidden, as [`Pressure::draw`] fills the
+    /// blob instead.
+    pub fn build(&self, first: usize) -> (Vec<Entity>, Vec<Stick>, Pressure) {
+        let entities: Vec<_> = (0..self.particles)
+            .map(|i| {
+                let angle = TAU * i as f32 / self.particles as f32;
+                let position = self.center + Vec2::new(angle.cos(), angle.sin()) * self.radius;
+                Entity::new(self.particle_radius, Motion::new(position.x, position.y))
+                    .with_group(first)
+                    .hidden()
+            })
+            .collect();
+
+        let ring = first..first + self.particles;
+        let sticks = ring
+            .clone()
+            .map(|i| {
+                let next = first + (i - first + 1) % self.particles;
+                let length = entities[i - first]
+                    .motion
+                    .position
+                    .distance(entities[next - first].motion.position);
+                Stick::new(i, next, length)
+                    .with_stiffness(self.rim_stiffness)
+                    .hidden()
+            })
+            .collect();
+
+        let outline: Vec<_> = entities
+            .iter()
+            .map(|entity| entity.motion.position)
+            .collect();
+        let pressure = Pressure {
+            ring,
+            target: signed_area(&outline) * self.inflation,
+            stiffness: self.pressure_stiffness,
+        };
+
+        (entities, sticks, pressure)
+    }
+
+    /// Adds the blob to `world`, returning the indices of its particles.
+    pub fn add_to(&self, world: &mut World) -> Range<usize> {
+        let first = world.entities.len();
+        let (entities, sticks, pressure) = self.build(first);
+        world
+            .entities
+            .extend(entities.into_iter().map(RefCell::new));
+        world.sticks.extend(sticks);
+        world.pressures.push(pressure);
+        first..world.entities.len()
+    }
+}
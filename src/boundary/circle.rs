@@ -0,0 +1,69 @@
+use super::{outward, Boundary, Projection};
+use crate::{Aabb, Material};
+use glam::Vec2;
+
+#[cfg(feature = "render")]
+use macroquad::{color::Color, shapes};
+
+/// A circular container that keeps entities entirely inside its radius, or a
+/// round peg when [`Inverted`](super::Inverted).
+#[derive(Debug, Clone)]
+pub struct Circle {
+    pub position: Vec2,
+    pub radius: f32,
+    pub material: Material,
+}
+
+impl Circle {
+    pub fn new(position: Vec2, radius: f32) -> Self {
+        Self {
+            position,
+            radius,
+            material: Material::default(),
+        }
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+}
+
+impl Boundary for Circle {
+    fn project(&self, point: Vec2) -> Projection {
+        let to_point = point - self.position;
+        let normal = outward(to_point, false, Vec2::Y);
+
+        Projection {
+            point: self.position + normal * self.radius,
+            normal,
+            inside: to_point.length() < self.radius,
+        }
+    }
+
+    fn bounds(&self) -> Aabb {
+        Aabb::from_circle(self.position, self.radius)
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    #[cfg(feature = "render")]
+    fn draw(&self, color: Color) {
+        shapes::draw_poly(
+            self.position.x,
+            self.position.y,
+            100,
+            self.radius,
+            0.0,
+            color,
+        );
+    }
+}
+
+impl Default for Circle {
+    fn default() -> Self {
+        Self::new(Vec2::new(800.0, 450.0), 400.0)
+    }
+}
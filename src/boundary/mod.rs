@@ -0,0 +1,259 @@
+mod circle;
+mod moving;
+mod polygon;
+mod rectangle;
+mod segment;
+
+pub use circle::Circle;
+pub use moving::{Moving, Transform};
+pub use polygon::Polygon;
+pub use rectangle::Rectangle;
+pub use segment::Segment;
+
+use crate::{collision::collide, geometry::closest_on_segment, Aabb, Material};
+use glam::Vec2;
+use std::fmt;
+
+#[cfg(feature = "render")]
+use macroquad::color::Color;
+
+/// The point on a boundary's outline nearest to some query point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projection {
+    pub point: Vec2,
+    /// Unit normal at `point`, facing out of the shape.
+    pub normal: Vec2,
+    /// Whether the query point lies inside the shape.
+    pub inside: bool,
+}
+
+/// How far, and in which direction, an entity has to move to stop
+/// overlapping a boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    pub normal: Vec2,
+    pub depth: f32,
+    /// Deepest point of the entity, where the push is applied.
+    pub point: Vec2,
+}
+
+/// A shape that entities collide with, static unless it says otherwise.
+///
+/// Containers keep entities inside their outline, while obstacles (see
+/// [`Inverted`]) keep them outside. Implementations only have to describe
+/// their outline through [`Boundary::project`]; the contact itself is derived
+/// from that. Boundaries that move (see [`Moving`]) also report the velocity
+/// of their outline.
+pub trait Boundary: fmt::Debug {
+    fn project(&self, point: Vec2) -> Projection;
+
+    /// Box enclosing the outline, used to skip obstacles that are far away.
+    fn bounds(&self) -> Aabb;
+
+    fn material(&self) -> &Material;
+
+    /// Whether entities belong inside the shape rather than outside it.
+    fn is_container(&self) -> bool {
+        true
+    }
+
+    /// Velocity of the outline at `point`, which contacts pass on to the
+    /// entities touching it.
+    fn velocity_at(&self, _point: Vec2) -> Vec2 {
+        Vec2::ZERO
+    }
+
+    /// Moves the boundary on by a step of `dt`.
+    fn advance(&mut self, _dt: f32) {}
+
+    /// Contact for a circle of `radius` centred at `center`, if it overlaps
+    /// the outline or sits on the wrong side of it.
+    fn contact(&self, center: Vec2, radius: f32) -> Option<Contact> {
+        if !self.is_container() && !self.bounds().overlaps(&Aabb::from_circle(center, radius)) {
+            return None;
+        }
+
+        let projection = self.project(center);
+        let distance = center.distance(projection.point);
+
+        // Distance from the outline, negative when on the wrong side of it.
+        let (clearance, normal) = match (self.is_container(), projection.inside) {
+            (true, true) => (distance, -projection.normal),
+            (true, false) => (-distance, -projection.normal),
+            (false, false) => (distance, projection.normal),
+            (false, true) => (-distance, projection.normal),
+        };
+
+        (clearance < radius).then_some(Contact {
+            normal,
+            depth: radius - clearance,
+            point: center - normal * radius,
+        })
+    }
+
+    /// Contact for a capsule: the segment `start`–`end` thickened by `radius`.
+    ///
+    /// The default checks both ends and, for obstacles, the point of the
+    /// segment nearest to the outline, keeping the deepest contact. That is
+    /// exact for straight walls and close enough for gently curved ones.
+    fn contact_capsule(&self, start: Vec2, end: Vec2, radius: f32) -> Option<Contact> {
+        if start == end {
+            return self.contact(start, radius);
+        }
+        if !self.is_container()
+            && !self
+                .bounds()
+                .overlaps(&Aabb::from_capsule(start, end, radius))
+        {
+            return None;
+        }
+
+        let nearest = (!self.is_container()).then(|| {
+            let outline = self.project((start + end) / 2.0).point;
+            closest_on_segment(outline, start, end)
+        });
+
+        [Some(start), Some(end), nearest]
+            .into_iter()
+            .flatten()
+            .filter_map(|point| self.contact(point, radius))
+            .max_by(|a, b| a.depth.total_cmp(&b.depth))
+    }
+
+    /// Appends the contacts for an entity's hull (see [`Entity::hull`])
+    /// thickened by `radius`.
+    ///
+    /// The default treats one or two points as a capsule and checks every
+    /// corner of a larger hull on its own. An obstacle can also poke into a
+    /// face between the corners, so the point of its outline nearest the
+    /// middle of the hull is checked against the hull too.
+    ///
+    /// [`Entity::hull`]: crate::Entity::hull
+    fn contacts(&self, hull: &[Vec2], radius: f32, contacts: &mut Vec<Contact>) {
+        match *hull {
+            [] => {}
+            [point] => contacts.extend(self.contact(point, radius)),
+            [start, end] => contacts.extend(self.contact_capsule(start, end, radius)),
+            _ => {
+                contacts.extend(
+                    hull.iter()
+                        .filter_map(|&corner| self.contact(corner, radius)),
+                );
+                if self.is_container() {
+                    return;
+                }
+
+                let bounds = hull
+                    .iter()
+                    .fold(Aabb::new(hull[0], hull[0]), |bounds, &corner| {
+                        bounds.union(&Aabb::new(corner, corner))
+                    })
+                    .expand(radius);
+                if !self.bounds().overlaps(&bounds) {
+                    return;
+                }
+
+                let middle =
+                    hull.iter().fold(Vec2::ZERO, |sum, &corner| sum + corner) / hull.len() as f32;
+                let outline = self.project(middle).point;
+                if let Some(manifold) = collide(hull, radius, &[outline], 0.0) {
+                    contacts.extend(manifold.points().iter().map(|point| Contact {
+                        normal: manifold.normal,
+                        depth: point.depth,
+                        point: point.position,
+                    }));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "render")]
+    fn draw(&self, color: Color);
+}
+
+/// Turns a container into a solid obstacle, or the other way around.
+#[derive(Debug, Clone)]
+pub struct Inverted<B>(pub B);
+
+impl<B: Boundary> Boundary for Inverted<B> {
+    fn project(&self, point: Vec2) -> Projection {
+        self.0.project(point)
+    }
+
+    fn bounds(&self) -> Aabb {
+        self.0.bounds()
+    }
+
+    fn material(&self) -> &Material {
+        self.0.material()
+    }
+
+    fn is_container(&self) -> bool {
+        !self.0.is_container()
+    }
+
+    fn velocity_at(&self, point: Vec2) -> Vec2 {
+        self.0.velocity_at(point)
+    }
+
+    fn advance(&mut self, dt: f32) {
+        self.0.advance(dt)
+    }
+
+    #[cfg(feature = "render")]
+    fn draw(&self, color: Color) {
+        self.0.draw(color)
+    }
+}
+
+impl<B: Boundary + ?Sized> Boundary for Box<B> {
+    fn project(&self, point: Vec2) -> Projection {
+        self.as_ref().project(point)
+    }
+
+    fn bounds(&self) -> Aabb {
+        self.as_ref().bounds()
+    }
+
+    fn material(&self) -> &Material {
+        self.as_ref().material()
+    }
+
+    fn is_container(&self) -> bool {
+        self.as_ref().is_container()
+    }
+
+    fn velocity_at(&self, point: Vec2) -> Vec2 {
+        self.as_ref().velocity_at(point)
+    }
+
+    fn advance(&mut self, dt: f32) {
+        self.as_mut().advance(dt)
+    }
+
+    fn contact(&self, center: Vec2, radius: f32) -> Option<Contact> {
+        self.as_ref().contact(center, radius)
+    }
+
+    fn contact_capsule(&self, start: Vec2, end: Vec2, radius: f32) -> Option<Contact> {
+        self.as_ref().contact_capsule(start, end, radius)
+    }
+
+    fn contacts(&self, hull: &[Vec2], radius: f32, contacts: &mut Vec<Contact>) {
+        self.as_ref().contacts(hull, radius, contacts)
+    }
+
+    #[cfg(feature = "render")]
+    fn draw(&self, color: Color) {
+        self.as_ref().draw(color)
+    }
+}
+
+/// Outward normal used when the query point sits exactly on the outline.
+fn outward(direction: Vec2, inside: bool, fallback: Vec2) -> Vec2 {
+    match direction.try_normalize() {
+        Some(direction) if inside => -direction,
+        Some(direction) => direction,
+        None => fallback,
+    }
+}
@@ -0,0 +1,241 @@
+use super::{Boundary, Contact, Projection};
+use crate::{geometry::rotate, Aabb, Material};
+use glam::Vec2;
+use std::fmt;
+
+#[cfg(feature = "render")]
+use macroquad::{
+    color::Color,
+    math::{Mat4, Vec3},
+    window,
+};
+
+/// How far a [`Moving`] boundary is shifted, turned and scaled from the shape
+/// it wraps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub offset: Vec2,
+    /// Turn about the pivot in radians, clockwise on screen.
+    pub angle: f32,
+    /// Uniform scale about the pivot.
+    pub scale: f32,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self {
+        offset: Vec2::ZERO,
+        angle: 0.0,
+        scale: 1.0,
+    };
+
+    pub fn shifted(offset: Vec2) -> Self {
+        Self {
+            offset,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn turned(angle: f32) -> Self {
+        Self {
+            angle,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn scaled(scale: f32) -> Self {
+        Self {
+            scale,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Where a point of the shape ends up.
+    fn apply(&self, pivot: Vec2, point: Vec2) -> Vec2 {
+        pivot + self.offset + rotate(point - pivot, self.angle) * self.scale
+    }
+
+    /// The point of the shape that ends up at `point`.
+    fn invert(&self, pivot: Vec2, point: Vec2) -> Vec2 {
+        pivot + rotate(point - pivot - self.offset, -self.angle) / self.scale
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Moves a boundary over time: a spinning drum, a shaking box or a
+/// breathing container.
+///
+/// The wrapped shape stays as built and every query is carried into its frame,
+/// so any boundary can move. The outline's velocity is passed on through
+/// contacts, letting the walls carry, fling and drag entities along.
+pub struct Moving<B> {
+    pub shape: B,
+    /// Point of the shape it turns and scales about.
+    pub pivot: Vec2,
+    /// Seconds since the boundary started moving.
+    pub time: f32,
+    path: Box<dyn Fn(f32) -> Transform>,
+    current: Transform,
+    previous: Transform,
+    previous_dt: Option<f32>,
+}
+
+impl<B: Boundary> Moving<B> {
+    /// Moves `shape` by the transform `path` gives for each time in seconds,
+    /// starting at time zero.
+    pub fn new(shape: B, pivot: Vec2, path: impl Fn(f32) -> Transform + 'static) -> Self {
+        let current = path(0.0);
+        Self {
+            shape,
+            pivot,
+            time: 0.0,
+            path: Box::new(path),
+            current,
+            previous: current,
+            previous_dt: None,
+        }
+    }
+
+    /// Spins `shape` about `pivot` at a steady `angular_velocity`.
+    pub fn spinning(shape: B, pivot: Vec2, angular_velocity: f32) -> Self {
+        Self::new(shape, pivot, move |time| {
+            Transform::turned(time * angular_velocity)
+        })
+    }
+
+    /// The transform the boundary is at now.
+    pub fn transform(&self) -> Transform {
+        self.current
+    }
+
+    fn in_shape_frame(&self, point: Vec2) -> Vec2 {
+        self.current.invert(self.pivot, point)
+    }
+
+    /// A contact found in the shape's frame, carried back out of it.
+    fn out_of_shape_frame(&self, contact: Contact) -> Contact {
+        Contact {
+            normal: rotate(contact.normal, self.current.angle),
+            depth: contact.depth * self.current.scale,
+            point: self.current.apply(self.pivot, contact.point),
+        }
+    }
+}
+
+impl<B: Boundary> Boundary for Moving<B> {
+    fn project(&self, point: Vec2) -> Projection {
+        let projection = self.shape.project(self.in_shape_frame(point));
+        Projection {
+            point: self.current.apply(self.pivot, projection.point),
+            normal: rotate(projection.normal, self.current.angle),
+            inside: projection.inside,
+        }
+    }
+
+    fn bounds(&self) -> Aabb {
+        let Aabb { min, max } = self.shape.bounds();
+        let corner = |x: f32, y: f32| {
+            let point = self.current.apply(self.pivot, Vec2::new(x, y));
+            Aabb::new(point, point)
+        };
+        corner(min.x, min.y)
+            .union(&corner(max.x, min.y))
+            .union(&corner(max.x, max.y))
+            .union(&corner(min.x, max.y))
+    }
+
+    fn material(&self) -> &Material {
+        self.shape.material()
+    }
+
+    fn is_container(&self) -> bool {
+        self.shape.is_container()
+    }
+
+    fn contact(&self, center: Vec2, radius: f32) -> Option<Contact> {
+        let radius = radius / self.current.scale;
+        self.shape
+            .contact(self.in_shape_frame(center), radius)
+            .map(|contact| self.out_of_shape_frame(contact))
+    }
+
+    fn contact_capsule(&self, start: Vec2, end: Vec2, radius: f32) -> Option<Contact> {
+        let (start, end) = (self.in_shape_frame(start), self.in_shape_frame(end));
+        self.shape
+            .contact_capsule(start, end, radius / self.current.scale)
+            .map(|contact| self.out_of_shape_frame(contact))
+    }
+
+    /// Hands the hull to the shape in its own frame, so shapes with their own
+    /// contacts, like one-way segments, keep them while moving.
+    fn contacts(&self, hull: &[Vec2], radius: f32, contacts: &mut Vec<Contact>) {
+        let local: Vec<_> = hull
+            .iter()
+            .map(|&point| self.in_shape_frame(point))
+            .collect();
+        let first = contacts.len();
+        self.shape
+            .contacts(&local, radius / self.current.scale, contacts);
+        for contact in &mut contacts[first..] {
+            *contact = self.out_of_shape_frame(*contact);
+        }
+    }
+
+    /// Where the point of the outline now at `point` was a step ago, over
+    /// that step.
+    fn velocity_at(&self, point: Vec2) -> Vec2 {
+        match self.previous_dt {
+            Some(dt) => {
+                let local = self.in_shape_frame(point);
+                (point - self.previous.apply(self.pivot, local)) / dt
+            }
+            None => Vec2::ZERO,
+        }
+    }
+
+    fn advance(&mut self, dt: f32) {
+        self.time += dt;
+        self.previous = self.current;
+        self.current = (self.path)(self.time);
+        self.previous_dt = Some(dt);
+    }
+
+    #[cfg(feature = "render")]
+    fn draw(&self, color: Color) {
+        let Transform {
+            offset,
+            angle,
+            scale,
+        } = self.current;
+        let pivot = Vec3::new(self.pivot.x, self.pivot.y, 0.0);
+        let matrix = Mat4::from_translation(pivot + Vec3::new(offset.x, offset.y, 0.0))
+            * Mat4::from_rotation_z(angle)
+            * Mat4::from_scale(Vec3::new(scale, scale, 1.0))
+            * Mat4::from_translation(-pivot);
+
+        // The context is only borrowed for each call, never across the
+        // shape's own drawing.
+        unsafe { window::get_internal_gl() }
+            .quad_gl
+            .push_model_matrix(matrix);
+        self.shape.draw(color);
+        unsafe { window::get_internal_gl() }
+            .quad_gl
+            .pop_model_matrix();
+    }
+}
+
+impl<B: fmt::Debug> fmt::Debug for Moving<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Moving")
+            .field("shape", &self.shape)
+            .field("pivot", &self.pivot)
+            .field("time", &self.time)
+            .field("transform", &self.current)
+            .finish_non_exhaustive()
+    }
+}
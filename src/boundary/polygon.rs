@@ -0,0 +1,169 @@
+use super::{outward, Boundary, Projection};
+use crate::{
+    geometry::{closest_on_segment, edges, signed_area},
+    Aabb, Material,
+};
+use glam::Vec2;
+
+#[cfg(feature = "render")]
+use macroquad::{color::Color, math, shapes};
+
+/// A simple polygon, convex or concave, given by its vertices in either
+/// winding order.
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    pub material: Material,
+    vertices: Vec<Vec2>,
+    /// `1` when the vertices wind so the interior is left of each edge.
+    winding: f32,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Vec2>) -> Self {
+        assert!(
+            vertices.len() >= 3,
+            "a polygon needs at least three vertices"
+        );
+
+        Self {
+            material: Material::default(),
+            winding: signed_area(&vertices).signum(),
+            vertices,
+        }
+    }
+
+    /// A regular polygon with `sides` vertices on a circle of `radius`.
+    pub fn regular(center: Vec2, radius: f32, sides: usize) -> Self {
+        Self::star(center, radius, radius, sides)
+    }
+
+    /// A star alternating between `outer` and `inner` radii, with `points`
+    /// tips.
+    pub fn star(center: Vec2, outer: f32, inner: f32, points: usize) -> Self {
+        let count = if outer == inner { points } else { points * 2 };
+        let vertices = (0..count)
+            .map(|i| {
+                let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+                let radius = if i % 2 == 0 { outer } else { inner };
+                center + Vec2::new(angle.sin(), -angle.cos()) * radius
+            })
+            .collect();
+
+        Self::new(vertices)
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+
+    /// Even-odd test against a ray cast along +x.
+    pub fn contains(&self, point: Vec2) -> bool {
+        edges(&self.vertices)
+            .filter(|(a, b)| (a.y > point.y) != (b.y > point.y))
+            .filter(|(a, b)| point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x))
+            .count()
+            % 2
+            == 1
+    }
+
+    /// Splits the polygon into triangles by ear clipping.
+    #[cfg(feature = "render")]
+    fn triangles(&self) -> Vec<[Vec2; 3]> {
+        let mut remaining = self.vertices.clone();
+        let mut triangles = Vec::with_capacity(remaining.len() - 2);
+
+        while remaining.len() > 3 {
+            let n = remaining.len();
+            let ear = (0..n).find(|&i| {
+                let (a, b, c) = (
+                    remaining[(i + n - 1) % n],
+                    remaining[i],
+                    remaining[(i + 1) % n],
+                );
+                let convex = (b - a).perp_dot(c - b) * self.winding > 0.0;
+
+                convex
+                    && remaining
+                        .iter()
+                        .filter(|&&p| p != a && p != b && p != c)
+                        .all(|&p| !in_triangle(p, a, b, c))
+            });
+
+            // Degenerate input, e.g. self-intersecting; draw what is left as a fan.
+            let i = match ear {
+                Some(i) => i,
+                None => break,
+            };
+            triangles.push([
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            ]);
+            remaining.remove(i);
+        }
+
+        triangles.extend(
+            (1..remaining.len() - 1).map(|i| [remaining[0], remaining[i], remaining[i + 1]]),
+        );
+        triangles
+    }
+}
+
+impl Boundary for Polygon {
+    fn project(&self, point: Vec2) -> Projection {
+        let inside = self.contains(point);
+        let (closest, edge) = edges(&self.vertices)
+            .map(|(a, b)| (closest_on_segment(point, a, b), b - a))
+            .min_by(|a, b| {
+                point
+                    .distance_squared(a.0)
+                    .total_cmp(&point.distance_squared(b.0))
+            })
+            .unwrap();
+        let edge_normal = -edge.perp().normalize_or_zero() * self.winding;
+
+        Projection {
+            point: closest,
+            normal: outward(point - closest, inside, edge_normal),
+            inside,
+        }
+    }
+
+    fn bounds(&self) -> Aabb {
+        let first = Aabb::new(self.vertices[0], self.vertices[0]);
+        self.vertices
+            .iter()
+            .fold(first, |bounds, &v| bounds.union(&Aabb::new(v, v)))
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    #[cfg(feature = "render")]
+    fn draw(&self, color: Color) {
+        // macroquad has its own copy of glam, so its points are a different
+        // type from ours.
+        let point = |v: Vec2| math::vec2(v.x, v.y);
+        for [a, b, c] in self.triangles() {
+            shapes::draw_triangle(point(a), point(b), point(c), color);
+        }
+    }
+}
+
+#[cfg(feature = "render")]
+fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let (d1, d2, d3) = (
+        (b - a).perp_dot(p - a),
+        (c - b).perp_dot(p - b),
+        (a - c).perp_dot(p - c),
+    );
+    let has_negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_negative && has_positive)
+}
@@ -0,0 +1,93 @@
+use super::{outward, Boundary, Projection};
+use crate::{Aabb, Material};
+use glam::Vec2;
+
+#[cfg(feature = "render")]
+use macroquad::{color::Color, shapes};
+
+/// An axis-aligned box.
+#[derive(Debug, Clone)]
+pub struct Rectangle {
+    pub min: Vec2,
+    pub max: Vec2,
+    pub material: Material,
+}
+
+impl Rectangle {
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self {
+            min,
+            max,
+            material: Material::default(),
+        }
+    }
+
+    pub fn from_center(center: Vec2, size: Vec2) -> Self {
+        Self::new(center - size * 0.5, center + size * 0.5)
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+}
+
+impl Boundary for Rectangle {
+    fn project(&self, point: Vec2) -> Projection {
+        let clamped = point.clamp(self.min, self.max);
+
+        if clamped != point {
+            return Projection {
+                point: clamped,
+                normal: outward(point - clamped, false, Vec2::Y),
+                inside: false,
+            };
+        }
+
+        // Inside: the nearest point is on whichever edge is closest.
+        [
+            (
+                point.x - self.min.x,
+                Vec2::new(self.min.x, point.y),
+                -Vec2::X,
+            ),
+            (
+                self.max.x - point.x,
+                Vec2::new(self.max.x, point.y),
+                Vec2::X,
+            ),
+            (
+                point.y - self.min.y,
+                Vec2::new(point.x, self.min.y),
+                -Vec2::Y,
+            ),
+            (
+                self.max.y - point.y,
+                Vec2::new(point.x, self.max.y),
+                Vec2::Y,
+            ),
+        ]
+        .into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, point, normal)| Projection {
+            point,
+            normal,
+            inside: true,
+        })
+        .unwrap()
+    }
+
+    fn bounds(&self) -> Aabb {
+        Aabb::new(self.min, self.max)
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    #[cfg(feature = "render")]
+    fn draw(&self, color: Color) {
+        let size = self.max - self.min;
+        shapes::draw_rectangle(self.min.x, self.min.y, size.x, size.y, color);
+    }
+}
@@ -0,0 +1,144 @@
+use super::{outward, Boundary, Contact, Projection};
+use crate::{
+    collision::collide,
+    geometry::{closest_between_segments, closest_on_segment},
+    Aabb, Material,
+};
+use glam::Vec2;
+
+#[cfg(feature = "render")]
+use macroquad::{color::Color, shapes};
+
+/// A line segment from `start` to `end`, thickened by `radius`, that entities
+/// stay clear of. Useful for ramps, walls and funnels.
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub radius: f32,
+    pub material: Material,
+    /// Only blocks entities whose centre lies on the solid side, see
+    /// [`Segment::one_way`].
+    pub one_way: bool,
+}
+
+impl Segment {
+    pub fn new(start: Vec2, end: Vec2, radius: f32) -> Self {
+        Self {
+            start,
+            end,
+            radius,
+            material: Material::default(),
+            one_way: false,
+        }
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Makes the segment a one-way platform, solid only on the side to the
+    /// left of `start` → `end` as seen on screen. A platform drawn from left
+    /// to right therefore holds entities from above and lets them jump up
+    /// through it from below.
+    pub fn one_way(mut self) -> Self {
+        self.one_way = true;
+        self
+    }
+
+    /// Closest point to `point` on the centre line.
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        closest_on_segment(point, self.start, self.end)
+    }
+
+    /// Unit normal of the solid side of a one-way platform.
+    fn solid_side(&self) -> Vec2 {
+        // Screen space has y pointing down, which mirrors `perp`.
+        -(self.end - self.start)
+            .perp()
+            .try_normalize()
+            .unwrap_or(Vec2::Y)
+    }
+}
+
+impl Boundary for Segment {
+    fn project(&self, point: Vec2) -> Projection {
+        let closest = self.closest_point(point);
+        let normal = outward(point - closest, false, self.solid_side());
+
+        Projection {
+            point: closest + normal * self.radius,
+            normal,
+            inside: point.distance(closest) < self.radius,
+        }
+    }
+
+    fn bounds(&self) -> Aabb {
+        Aabb::from_capsule(self.start, self.end, self.radius)
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn is_container(&self) -> bool {
+        false
+    }
+
+    fn contact(&self, center: Vec2, radius: f32) -> Option<Contact> {
+        self.contact_capsule(center, center, radius)
+    }
+
+    fn contact_capsule(&self, start: Vec2, end: Vec2, radius: f32) -> Option<Contact> {
+        let (point, closest) = closest_between_segments(start, end, self.start, self.end);
+        let offset = point - closest;
+        let clearance = offset.length() - self.radius;
+
+        if clearance >= radius {
+            return None;
+        }
+        if self.one_way && offset.dot(self.solid_side()) <= 0.0 {
+            return None;
+        }
+
+        let normal = outward(offset, false, self.solid_side());
+        Some(Contact {
+            normal,
+            depth: radius - clearance,
+            point: point - normal * radius,
+        })
+    }
+
+    fn contacts(&self, hull: &[Vec2], radius: f32, contacts: &mut Vec<Contact>) {
+        let manifold = match collide(hull, radius, &[self.start, self.end], self.radius) {
+            Some(manifold) => manifold,
+            None => return,
+        };
+        if self.one_way && manifold.normal.dot(self.solid_side()) <= 0.0 {
+            return;
+        }
+
+        contacts.extend(manifold.points().iter().map(|point| Contact {
+            normal: manifold.normal,
+            depth: point.depth,
+            point: point.position,
+        }));
+    }
+
+    #[cfg(feature = "render")]
+    fn draw(&self, color: Color) {
+        let thickness = (self.radius * 2.0).max(1.0);
+        shapes::draw_line(
+            self.start.x,
+            self.start.y,
+            self.end.x,
+            self.end.y,
+            thickness,
+            color,
+        );
+        for cap in [self.start, self.end] {
+            shapes::draw_circle(cap.x, cap.y, self.radius, color);
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use super::Broadphase;
+use crate::{Aabb, Entity};
+use std::cell::RefCell;
+
+/// A dynamic bounding volume hierarchy.
+///
+/// Every entity owns a leaf holding its bounds enlarged by `margin`. The tree
+/// is only touched when an entity escapes its enlarged box, in which case the
+/// leaf is removed and reinserted next to the sibling that grows the total
+/// perimeter the least, with tree rotations keeping the height logarithmic.
+/// This keeps updates cheap for slow moving bodies and handles radii spanning
+/// several orders of magnitude, since nothing about the tree depends on a
+/// fixed cell size.
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    pub margin: f32,
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    leaves: Vec<usize>,
+    stack: Vec<usize>,
+    candidates: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    bounds: Aabb,
+    parent: Option<usize>,
+    height: usize,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Leaf(usize),
+    Branch(usize, usize),
+}
+
+impl Bvh {
+    pub fn new(margin: f32) -> Self {
+        Self {
+            margin,
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            leaves: Vec::new(),
+            stack: Vec::new(),
+            candidates: Vec::new(),
+        }
+    }
+
+    /// Brings the tree in line with the current entity bounds.
+    pub fn update(&mut self, entities: &[RefCell<Entity>]) {
+        while self.leaves.len() > entities.len() {
+            let leaf = self.leaves.pop().unwrap();
+            self.remove(leaf);
+        }
+
+        for (i, entity) in entities.iter().enumerate() {
+            let bounds = entity.borrow().bounds();
+
+            match self.leaves.get(i).copied() {
+                Some(leaf) if self.nodes[leaf].bounds.contains(&bounds) => {}
+                Some(leaf) => {
+                    self.remove(leaf);
+                    self.leaves[i] = self.insert(i, bounds.expand(self.margin));
+                }
+                None => {
+                    let leaf = self.insert(i, bounds.expand(self.margin));
+                    self.leaves.push(leaf);
+                }
+            }
+        }
+    }
+
+    /// Collects every entity whose enlarged bounds overlap `region`. The
+    /// result may contain entities that are near, but not inside, the region.
+    fn candidates(&mut self, region: &Aabb, results: &mut Vec<usize>) {
+        self.stack.clear();
+        self.stack.extend(self.root);
+
+        while let Some(index) = self.stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.overlaps(region) {
+                continue;
+            }
+
+            match node.kind {
+                Kind::Leaf(entity) => results.push(entity),
+                Kind::Branch(left, right) => self.stack.extend([left, right]),
+            }
+        }
+    }
+
+    /// Number of nodes on the longest path from the root to a leaf.
+    pub fn depth(&self) -> usize {
+        self.root.map_or(0, |root| self.nodes[root].height + 1)
+    }
+
+    fn allocate(&mut self, node: Node) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn insert(&mut self, entity: usize, bounds: Aabb) -> usize {
+        let leaf = self.allocate(Node {
+            bounds,
+            parent: None,
+            height: 0,
+            kind: Kind::Leaf(entity),
+        });
+
+        let mut sibling = match self.root {
+            Some(root) => root,
+            None => {
+                self.root = Some(leaf);
+                return leaf;
+            }
+        };
+
+        // Walk down, stopping where pairing with the current node is cheaper
+        // than descending into either child.
+        while let Kind::Branch(left, right) = self.nodes[sibling].kind {
+            let perimeter = self.nodes[sibling].bounds.perimeter();
+            let combined = self.nodes[sibling].bounds.union(&bounds).perimeter();
+            let cost = 2.0 * combined;
+            let inheritance = 2.0 * (combined - perimeter);
+
+            let descend = |child: usize| {
+                let node = &self.nodes[child];
+                let grown = node.bounds.union(&bounds).perimeter();
+                match node.kind {
+                    Kind::Leaf(_) => grown + inheritance,
+                    Kind::Branch(..) => grown - node.bounds.perimeter() + inheritance,
+                }
+            };
+            let (cost_left, cost_right) = (descend(left), descend(right));
+
+            if cost < cost_left && cost < cost_right {
+                break;
+            }
+            sibling = if cost_left < cost_right { left } else { right };
+        }
+
+        let old_parent = self.nodes[sibling].parent;
+        let parent = self.allocate(Node {
+            bounds: self.nodes[sibling].bounds.union(&bounds),
+            parent: old_parent,
+            height: self.nodes[sibling].height + 1,
+            kind: Kind::Branch(sibling, leaf),
+        });
+        self.nodes[sibling].parent = Some(parent);
+        self.nodes[leaf].parent = Some(parent);
+
+        match old_parent {
+            Some(old_parent) => self.replace_child(old_parent, sibling, parent),
+            None => self.root = Some(parent),
+        }
+        self.refit(Some(parent));
+
+        leaf
+    }
+
+    fn remove(&mut self, leaf: usize) {
+        self.free.push(leaf);
+
+        let parent = match self.nodes[leaf].parent {
+            Some(parent) => parent,
+            None => {
+                self.root = None;
+                return;
+            }
+        };
+        let sibling = match self.nodes[parent].kind {
+            Kind::Branch(left, right) if left == leaf => right,
+            Kind::Branch(left, _) => left,
+            Kind::Leaf(_) => unreachable!("leaf nodes have no children"),
+        };
+        let grandparent = self.nodes[parent].parent;
+
+        self.free.push(parent);
+        self.nodes[sibling].parent = grandparent;
+        match grandparent {
+            Some(grandparent) => {
+                self.replace_child(grandparent, parent, sibling);
+                self.refit(Some(grandparent));
+            }
+            None => self.root = Some(sibling),
+        }
+    }
+
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        if let Kind::Branch(left, right) = &mut self.nodes[parent].kind {
+            if *left == old {
+                *left = new;
+            } else {
+                *right = new;
+            }
+        }
+    }
+
+    /// Walks from `index` up to the root, rebalancing and recomputing the
+    /// bounds of every node on the way.
+    fn refit(&mut self, mut index: Option<usize>) {
+        while let Some(current) = index {
+            let current = self.rebalance(current);
+            self.fit(current);
+            index = self.nodes[current].parent;
+        }
+    }
+
+    fn fit(&mut self, index: usize) {
+        if let Kind::Branch(left, right) = self.nodes[index].kind {
+            let (left, right) = (&self.nodes[left], &self.nodes[right]);
+            let bounds = left.bounds.union(&right.bounds);
+            let height = 1 + left.height.max(right.height);
+
+            self.nodes[index].bounds = bounds;
+            self.nodes[index].height = height;
+        }
+    }
+
+    /// Rotates the taller child of `index` into its place when the two
+    /// subtrees differ in height by more than one, returning the node now
+    /// occupying that position.
+    fn rebalance(&mut self, index: usize) -> usize {
+        let (left, right) = match self.nodes[index].kind {
+            Kind::Branch(left, right) => (left, right),
+            Kind::Leaf(_) => return index,
+        };
+        let balance = self.nodes[right].height as isize - self.nodes[left].height as isize;
+
+        if balance > 1 {
+            self.promote(index, right, left)
+        } else if balance < -1 {
+            self.promote(index, left, right)
+        } else {
+            index
+        }
+    }
+
+    /// Moves `child` into the place of its parent `index`. The old parent
+    /// keeps `other` and adopts the shorter of `child`'s two subtrees.
+    fn promote(&mut self, index: usize, child: usize, other: usize) -> usize {
+        let (a, b) = match self.nodes[child].kind {
+            Kind::Branch(a, b) => (a, b),
+            Kind::Leaf(_) => return index,
+        };
+        let (tall, short) = if self.nodes[a].height > self.nodes[b].height {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        let parent = self.nodes[index].parent;
+        self.nodes[child].parent = parent;
+        match parent {
+            Some(parent) => self.replace_child(parent, index, child),
+            None => self.root = Some(child),
+        }
+
+        self.nodes[child].kind = Kind::Branch(index, tall);
+        self.nodes[index].parent = Some(child);
+        self.nodes[index].kind = Kind::Branch(other, short);
+        self.nodes[short].parent = Some(index);
+
+        self.fit(index);
+        self.fit(child);
+        child
+    }
+}
+
+impl Default for Bvh {
+    fn default() -> Self {
+        Self::new(2.0)
+    }
+}
+
+impl Broadphase for Bvh {
+    fn name(&self) -> &'static str {
+        "bvh"
+    }
+
+    fn pairs(&mut self, entities: &[RefCell<Entity>], pairs: &mut Vec<(usize, usize)>) {
+        pairs.clear();
+        self.update(entities);
+
+        let mut candidates = std::mem::take(&mut self.candidates);
+        for (a, entity) in entities.iter().enumerate() {
+            candidates.clear();
+            self.candidates(&entity.borrow().bounds(), &mut candidates);
+            pairs.extend(candidates.iter().filter(|&&b| b > a).map(|&b| (a, b)));
+        }
+        self.candidates = candidates;
+    }
+
+    fn query(&mut self, entities: &[RefCell<Entity>], region: &Aabb, results: &mut Vec<usize>) {
+        self.update(entities);
+
+        let start = results.len();
+        self.candidates(region, results);
+        results[start..].sort_unstable();
+
+        let mut kept = start;
+        for i in start..results.len() {
+            if entities[results[i]].borrow().bounds().overlaps(region) {
+                results[kept] = results[i];
+                kept += 1;
+            }
+        }
+        results.truncate(kept);
+    }
+}
@@ -0,0 +1,113 @@
+use super::Broadphase;
+use crate::Entity;
+use glam::Vec2;
+use std::cell::RefCell;
+
+/// A spatial hash over a uniform grid.
+///
+/// Each entity is bucketed by the cell containing the centre of its bounds,
+/// which for a polygon is not its centroid, and candidates
+/// are gathered from the surrounding 3x3 block of cells. This is exact as long
+/// as the cell is at least as wide as the largest entity bounds, which is what
+/// the cell size is derived from unless one is given explicitly.
+#[derive(Debug, Clone, Default)]
+pub struct UniformGrid {
+    pub cell_size: Option<f32>,
+    cells: Vec<(i32, i32)>,
+    bucket_start: Vec<usize>,
+    bucket_entries: Vec<usize>,
+}
+
+impl UniformGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_cell_size(cell_size: f32) -> Self {
+        Self {
+            cell_size: Some(cell_size),
+            ..Self::default()
+        }
+    }
+
+    fn cell(position: Vec2, cell_size: f32) -> (i32, i32) {
+        let cell = (position / cell_size).floor();
+        (cell.x as i32, cell.y as i32)
+    }
+
+    fn bucket(cell: (i32, i32), mask: usize) -> usize {
+        let hash =
+            (cell.0 as u32).wrapping_mul(0x9e37_79b1) ^ (cell.1 as u32).wrapping_mul(0x85eb_ca77);
+        hash as usize & mask
+    }
+}
+
+impl Broadphase for UniformGrid {
+    fn name(&self) -> &'static str {
+        "uniform grid"
+    }
+
+    fn pairs(&mut self, entities: &[RefCell<Entity>], pairs: &mut Vec<(usize, usize)>) {
+        pairs.clear();
+        if entities.is_empty() {
+            return;
+        }
+
+        let cell_size = self.cell_size.unwrap_or_else(|| {
+            let largest = entities
+                .iter()
+                .map(|entity| entity.borrow().bounds().size().max_element())
+                .fold(0.0, f32::max);
+            // Points only touch where they coincide, so any cell size will
+            // do, but one too small for the positions overflows the cells.
+            if largest > 0.0 {
+                largest
+            } else {
+                1.0
+            }
+        });
+
+        self.cells.clear();
+        self.cells.extend(
+            entities
+                .iter()
+                .map(|entity| Self::cell(entity.borrow().bounds().center(), cell_size)),
+        );
+
+        // Counting sort of entity indices by bucket, so each bucket is the
+        // contiguous run `bucket_start[b]..bucket_start[b + 1]`.
+        let buckets = (entities.len() * 2).next_power_of_two();
+        let mask = buckets - 1;
+        self.bucket_start.clear();
+        self.bucket_start.resize(buckets + 1, 0);
+        for &cell in &self.cells {
+            self.bucket_start[Self::bucket(cell, mask)] += 1;
+        }
+        for i in 0..buckets {
+            self.bucket_start[i + 1] += self.bucket_start[i];
+        }
+        self.bucket_entries.resize(entities.len(), 0);
+        for (i, &cell) in self.cells.iter().enumerate() {
+            let start = &mut self.bucket_start[Self::bucket(cell, mask)];
+            *start -= 1;
+            self.bucket_entries[*start] = i;
+        }
+
+        for (a, &(x, y)) in self.cells.iter().enumerate() {
+            for neighbour in (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (x + dx, y + dy))) {
+                let bucket = Self::bucket(neighbour, mask);
+                let entries =
+                    &self.bucket_entries[self.bucket_start[bucket]..self.bucket_start[bucket + 1]];
+
+                // Distinct cells can share a bucket, so check the cell itself
+                // to avoid reporting a pair twice.
+                pairs.extend(
+                    entries
+                        .iter()
+                        .filter(|&&b| b > a && self.cells[b] == neighbour)
+                        .map(|&b| (a, b)),
+                );
+            }
+        }
+    }
+}
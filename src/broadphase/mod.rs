@@ -0,0 +1,52 @@
+mod bvh;
+mod grid;
+mod sweep;
+
+pub use bvh::Bvh;
+pub use grid::UniformGrid;
+pub use sweep::{Axis, SweepAndPrune};
+
+use crate::{Aabb, Entity};
+use std::{cell::RefCell, fmt};
+
+/// Finds the pairs of entities that might be touching, so the resolver only
+/// has to run the exact overlap test on those.
+///
+/// Implementations may keep state between calls to exploit temporal
+/// coherence, which is why `pairs` takes `&mut self`.
+pub trait Broadphase: fmt::Debug {
+    fn name(&self) -> &'static str;
+
+    /// Replaces the contents of `pairs` with candidate index pairs `(a, b)`,
+    /// each reported once with `a < b`.
+    fn pairs(&mut self, entities: &[RefCell<Entity>], pairs: &mut Vec<(usize, usize)>);
+
+    /// Appends, in ascending order, the indices of entities whose bounds
+    /// overlap `region`. The default scans every entity.
+    fn query(&mut self, entities: &[RefCell<Entity>], region: &Aabb, results: &mut Vec<usize>) {
+        results.extend(
+            entities
+                .iter()
+                .enumerate()
+                .filter(|(_, entity)| entity.borrow().bounds().overlaps(region))
+                .map(|(i, _)| i),
+        );
+    }
+}
+
+/// Tests every pair of entities, which is quadratic in the entity count.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BruteForce;
+
+impl Broadphase for BruteForce {
+    fn name(&self) -> &'static str {
+        "brute force"
+    }
+
+    fn pairs(&mut self, entities: &[RefCell<Entity>], pairs: &mut Vec<(usize, usize)>) {
+        pairs.clear();
+        for a in 0..entities.len() {
+            pairs.extend((a + 1..entities.len()).map(|b| (a, b)));
+        }
+    }
+}
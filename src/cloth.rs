@@ -0,0 +1,157 @@
+use crate::{Entity, Motion, Stick, World};
+use glam::Vec2;
+use std::{cell::RefCell, ops::Range};
+
+/// Builds a sheet of cloth: a rectangular lattice of particles hanging from
+/// `origin`, its top-left corner, held together by sticks.
+///
+/// Structural sticks join each particle to its neighbours along the rows and
+/// columns and are the only ones drawn, so the cloth shows as a line grid.
+/// Hidden shear sticks across each cell keep it from collapsing sideways and
+/// soft bend sticks spanning two cells resist folding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cloth {
+    pub origin: Vec2,
+    pub size: Vec2,
+    /// Particles along each row and down each column.
+    pub columns: usize,
+    pub rows: usize,
+    pub radius: f32,
+    /// Stiffness of the shear and bend sticks, see [`Stick::stiffness`]. The
+    /// structural ones are rigid.
+    pub shear_stiffness: f32,
+    pub bend_stiffness: f32,
+    /// Stretch ratio at which any stick tears, see [`Stick::tear_ratio`].
+    pub tear_ratio: Option<f32>,
+    pub pin_top_corners: bool,
+    pub pin_top_edge: bool,
+}
+
+impl Cloth {
+    pub fn new(origin: Vec2, size: Vec2, columns: usize, rows: usize) -> Self {
+        assert!(
+            columns >= 2 && rows >= 2,
+            "a cloth needs at least two particles each way"
+        );
+        Self {
+            origin,
+            size,
+            columns,
+            rows,
+            radius: 3.0,
+            shear_stiffness: f32::INFINITY,
+            bend_stiffness: 1000.0,
+            tear_ratio: None,
+            pin_top_corners: false,
+            pin_top_edge: false,
+        }
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_shear_stiffness(mut self, stiffness: f32) -> Self {
+        self.shear_stiffness = stiffness;
+        self
+    }
+
+    pub fn with_bend_stiffness(mut self, stiffness: f32) -> Self {
+        self.bend_stiffness = stiffness;
+        self
+    }
+
+    pub fn with_tearing(mut self, tear_ratio: f32) -> Self {
+        self.tear_ratio = Some(tear_ratio);
+        self
+    }
+
+    /// Hangs the cloth from its two top corners.
+    pub fn pin_top_corners(mut self) -> Self {
+        self.pin_top_corners = true;
+        self
+    }
+
+    /// Hangs the cloth from every particle along its top edge.
+    pub fn pin_top_edge(mut self) -> Self {
+        self.pin_top_edge = true;
+        self
+    }
+
+    /// Offset from the first particle of the one at `column` and `row`.
+    pub fn index(&self, column: usize, row: usize) -> usize {
+        row * self.columns + column
+    }
+
+    /// The particles, row by row from the top, and the sticks between them,
+    /// numbered as if the first particle will be stored at index `first`.
+    /// The particles share `first` as their [`Entity::group`].
+    pub fn build(&self, first: usize) -> (Vec<Entity>, Vec<Stick>) {
+        let spacing = self.size / Vec2::new((self.columns - 1) as f32, (self.rows - 1) as f32);
+        let pinned = |column: usize, row: usize| {
+            let corner = column == 0 || column == self.columns - 1;
+            row == 0 && (self.pin_top_edge || (self.pin_top_corners && corner))
+        };
+
+        let mut entities = Vec::with_capacity(self.columns * self.rows);
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let position = self.origin + spacing * Vec2::new(column as f32, row as f32);
+                let entity = Entity::new(self.radius, Motion::new(position.x, position.y))
+                    .with_group(first)
+                    .hidden();
+                entities.push(if pinned(column, row) {
+                    entity.pinned()
+                } else {
+                    entity
+                });
+            }
+        }
+
+        let mut sticks = Vec::new();
+        let mut link = |from: (usize, usize), to: (usize, usize), stiffness: f32, visible: bool| {
+            if to.0 >= self.columns || to.1 >= self.rows {
+                return;
+            }
+            let (a, b) = (self.index(from.0, from.1), self.index(to.0, to.1));
+            let length = entities[a]
+                .motion
+                .position
+                .distance(entities[b].motion.position);
+            let stick = Stick::new(first + a, first + b, length).with_stiffness(stiffness);
+            let stick = match self.tear_ratio {
+                Some(ratio) => stick.with_tearing(ratio),
+                None => stick,
+            };
+            sticks.push(if visible { stick } else { stick.hidden() });
+        };
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let here = (column, row);
+                link(here, (column + 1, row), f32::INFINITY, true);
+                link(here, (column, row + 1), f32::INFINITY, true);
+                link(here, (column + 1, row + 1), self.shear_stiffness, false);
+                if column > 0 {
+                    link(here, (column - 1, row + 1), self.shear_stiffness, false);
+                }
+                link(here, (column + 2, row), self.bend_stiffness, false);
+                link(here, (column, row + 2), self.bend_stiffness, false);
+            }
+        }
+
+        (entities, sticks)
+    }
+
+    /// Adds the cloth to `world`, returning the indices of its particles.
+    pub fn add_to(&self, world: &mut World) -> Range<usize> {
+        let first = world.entities.len();
+        let (entities, sticks) = self.build(first);
+        world
+            .entities
+            .extend(entities.into_iter().map(RefCell::new));
+        world.sticks.extend(sticks);
+        first..world.entities.len()
+    }
+}
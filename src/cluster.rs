@@ -0,0 +1,127 @@
+use crate::{geometry::rotate, Entity};
+use glam::Vec2;
+use std::{cell::RefCell, ops::Range};
+
+/// A meshless soft body: a group of entities pulled towards their rest shape,
+/// moved and turned to best fit where they are now.
+///
+/// Nothing links the particles pairwise, so a cluster deforms freely under a
+/// knock and flows back into shape. With plasticity, deformation past a yield
+/// distance sticks, denting the rest shape like clay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cluster {
+    /// Indices of the entities in the cluster.
+    pub particles: Range<usize>,
+    /// Rest position of each particle relative to the rest centre.
+    pub rest: Vec<Vec2>,
+    /// Fraction of the way to the fitted shape the particles move each step,
+    /// from `0` (loose) to `1` (rigid).
+    pub stiffness: f32,
+    /// Distance from its fitted position past which a particle starts to
+    /// move its rest position instead of only springing back.
+    pub yield_distance: Option<f32>,
+    /// Fraction of the excess over the yield distance taken into the rest
+    /// shape each step.
+    pub creep: f32,
+}
+
+impl Cluster {
+    /// Holds the entities in `particles` in their current arrangement.
+    pub fn new(entities: &[RefCell<Entity>], particles: Range<usize>) -> Self {
+        assert!(!particles.is_empty(), "a cluster needs at least one entity");
+        let positions: Vec<_> = particles
+            .clone()
+            .map(|index| entities[index].borrow().motion.position)
+            .collect();
+        let center = centroid(&positions);
+
+        Self {
+            particles,
+            rest: positions
+                .iter()
+                .map(|&position| position - center)
+                .collect(),
+            stiffness: 1.0,
+            yield_distance: None,
+            creep: 0.0,
+        }
+    }
+
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    pub fn with_plasticity(mut self, yield_distance: f32, creep: f32) -> Self {
+        self.yield_distance = Some(yield_distance);
+        self.creep = creep;
+        self
+    }
+
+    /// Centre and angle of the rigid transform that best maps the rest shape
+    /// onto the current positions.
+    pub fn fit(&self, entities: &[RefCell<Entity>]) -> (Vec2, f32) {
+        let positions = self.positions(entities);
+        let center = centroid(&positions);
+
+        // The rotation of the polar decomposition of the covariance between
+        // rest and current offsets, which in 2D reduces to one angle.
+        let (cos, sin) =
+            positions
+                .iter()
+                .zip(&self.rest)
+                .fold((0.0, 0.0), |(cos, sin), (&position, rest)| {
+                    let offset = position - center;
+                    (cos + offset.dot(*rest), sin + rest.perp_dot(offset))
+                });
+        (center, sin.atan2(cos))
+    }
+
+    /// Moves every dynamic particle part of the way to its fitted position.
+    /// Over `passes` calls in one step the particles cover `stiffness` of the
+    /// distance in total.
+    pub(crate) fn solve(&self, entities: &[RefCell<Entity>], passes: usize) {
+        let (center, angle) = self.fit(entities);
+        let fraction = 1.0 - (1.0 - self.stiffness.clamp(0.0, 1.0)).powf(1.0 / passes as f32);
+
+        for (index, rest) in self.particles.clone().zip(&self.rest) {
+            let mut entity = entities[index].borrow_mut();
+            if !entity.is_static() {
+                let goal = center + rotate(*rest, angle);
+                let position = entity.motion.position;
+                entity.motion.position += (goal - position) * fraction;
+            }
+        }
+    }
+
+    /// Takes deformation past the yield distance into the rest shape.
+    pub(crate) fn deform(&mut self, entities: &[RefCell<Entity>]) {
+        let yield_distance = match self.yield_distance {
+            Some(yield_distance) => yield_distance,
+            None => return,
+        };
+        let (center, angle) = self.fit(entities);
+
+        for (position, rest) in self.positions(entities).into_iter().zip(&mut self.rest) {
+            let deviation = rotate(position - center, -angle) - *rest;
+            let excess = deviation.length() - yield_distance;
+            if excess > 0.0 {
+                *rest += deviation.normalize() * excess * self.creep;
+            }
+        }
+
+        let center = centroid(&self.rest);
+        self.rest.iter_mut().for_each(|rest| *rest -= center);
+    }
+
+    fn positions(&self, entities: &[RefCell<Entity>]) -> Vec<Vec2> {
+        self.particles
+            .clone()
+            .map(|index| entities[index].borrow().motion.position)
+            .collect()
+    }
+}
+
+fn centroid(points: &[Vec2]) -> Vec2 {
+    points.iter().fold(Vec2::ZERO, |sum, &point| sum + point) / points.len() as f32
+}
@@ -0,0 +1,257 @@
+//! Contact generation between convex hulls thickened by a radius.
+//!
+//! Every entity shape reduces to such a hull: a circle is one vertex, a
+//! capsule two and a polygon its corners with no radius.
+
+use crate::geometry::{self, closest_between_segments};
+use glam::Vec2;
+
+/// Edges whose direction is within this cosine of perpendicular to the
+/// contact normal count as faces and produce two contact points.
+const FACE_TOLERANCE: f32 = 0.05;
+
+/// A point where two shapes overlap and by how much.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ContactPoint {
+    pub position: Vec2,
+    pub depth: f32,
+}
+
+/// Up to two contact points sharing one normal, which pushes the first shape
+/// away from the second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Manifold {
+    pub normal: Vec2,
+    points: [ContactPoint; 2],
+    len: usize,
+}
+
+impl Manifold {
+    fn single(normal: Vec2, point: ContactPoint) -> Self {
+        Self {
+            normal,
+            points: [point; 2],
+            len: 1,
+        }
+    }
+
+    pub fn points(&self) -> &[ContactPoint] {
+        &self.points[..self.len]
+    }
+}
+
+/// Contact manifold between hull `a` thickened by `radius_a` and hull `b`
+/// thickened by `radius_b`, or `None` when they are apart.
+///
+/// Overlapping hulls are separated along the axis of least penetration found
+/// by a separating-axis test. Disjoint hulls that only touch through their
+/// radii use the closest points between them instead.
+pub(crate) fn collide(a: &[Vec2], radius_a: f32, b: &[Vec2], radius_b: f32) -> Option<Manifold> {
+    let radius = radius_a + radius_b;
+    let (normal, depth) = match least_penetration(a, b) {
+        Some((normal, separation)) if separation < 0.0 => (normal, radius - separation),
+        _ => {
+            let (closest_a, closest_b) = closest_between_hulls(a, b);
+            let offset = closest_a - closest_b;
+            let distance = offset.length();
+            if distance >= radius || radius == 0.0 {
+                return None;
+            }
+
+            (offset.try_normalize().unwrap_or(Vec2::Y), radius - distance)
+        }
+    };
+
+    Some(manifold(a, radius_a, b, radius_b, normal, depth))
+}
+
+/// Axis, pointing from `b` towards `a`, along which the hulls overlap the
+/// least, with their separation along it. `None` when neither hull has an
+/// edge to test.
+fn least_penetration(a: &[Vec2], b: &[Vec2]) -> Option<(Vec2, f32)> {
+    axes(a)
+        .chain(axes(b))
+        .flat_map(|axis| [axis, -axis])
+        .map(|normal| {
+            let (min_a, _) = extent(a, normal);
+            let (_, max_b) = extent(b, normal);
+            (normal, min_a - max_b)
+        })
+        .max_by(|x, y| x.1.total_cmp(&y.1))
+}
+
+/// Candidate separating axes of a hull: its edge normals, plus the direction
+/// of a lone segment so points beyond its ends are not mistaken for overlaps.
+fn axes(hull: &[Vec2]) -> impl Iterator<Item = Vec2> + '_ {
+    let direction = match hull {
+        [start, end] => (*end - *start).try_normalize(),
+        _ => None,
+    };
+
+    edges(hull)
+        .filter_map(|(start, end)| (end - start).perp().try_normalize())
+        .chain(direction)
+}
+
+fn extent(hull: &[Vec2], axis: Vec2) -> (f32, f32) {
+    hull.iter()
+        .map(|vertex| vertex.dot(axis))
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), d| {
+            (min.min(d), max.max(d))
+        })
+}
+
+/// Edges of a hull. A segment is its own single edge and a point has none.
+fn edges(hull: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    let count = match hull.len() {
+        0 | 1 => 0,
+        2 => 1,
+        n => n,
+    };
+
+    geometry::edges(hull).take(count)
+}
+
+fn closest_between_hulls(a: &[Vec2], b: &[Vec2]) -> (Vec2, Vec2) {
+    features(a)
+        .flat_map(|(a0, a1)| {
+            features(b).map(move |(b0, b1)| closest_between_segments(a0, a1, b0, b1))
+        })
+        .min_by(|x, y| {
+            x.0.distance_squared(x.1)
+                .total_cmp(&y.0.distance_squared(y.1))
+        })
+        .expect("hulls have at least one vertex")
+}
+
+/// Edges of a hull, with a lone point as a degenerate edge of its own.
+fn features(hull: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    let point = match hull {
+        [point] => Some((*point, *point)),
+        _ => None,
+    };
+    point.into_iter().chain(edges(hull))
+}
+
+/// The part of a hull furthest along `direction`: a single vertex, or an
+/// edge when one lies flat against that direction.
+#[derive(Debug, Clone, Copy)]
+enum Feature {
+    Vertex(Vec2),
+    Edge(Vec2, Vec2),
+}
+
+fn feature(hull: &[Vec2], direction: Vec2) -> Feature {
+    let flat = |start: Vec2, end: Vec2| {
+        (end - start)
+            .try_normalize()
+            .is_some_and(|edge| edge.dot(direction).abs() < FACE_TOLERANCE)
+    };
+
+    let n = hull.len();
+    let support = (0..n)
+        .max_by(|&i, &j| hull[i].dot(direction).total_cmp(&hull[j].dot(direction)))
+        .expect("hulls have at least one vertex");
+
+    match n {
+        1 => Feature::Vertex(hull[0]),
+        2 if flat(hull[0], hull[1]) => Feature::Edge(hull[0], hull[1]),
+        2 => Feature::Vertex(hull[support]),
+        _ => {
+            let (previous, next) = (hull[(support + n - 1) % n], hull[(support + 1) % n]);
+            let vertex = hull[support];
+            let alignment = |other: Vec2| {
+                (other - vertex)
+                    .try_normalize()
+                    .map_or(f32::INFINITY, |edge| edge.dot(direction).abs())
+            };
+
+            if alignment(previous) <= alignment(next) && flat(previous, vertex) {
+                Feature::Edge(previous, vertex)
+            } else if flat(vertex, next) {
+                Feature::Edge(vertex, next)
+            } else {
+                Feature::Vertex(vertex)
+            }
+        }
+    }
+}
+
+fn manifold(
+    a: &[Vec2],
+    radius_a: f32,
+    b: &[Vec2],
+    radius_b: f32,
+    normal: Vec2,
+    depth: f32,
+) -> Manifold {
+    let radius = radius_a + radius_b;
+    let (feature_a, feature_b) = (feature(a, -normal), feature(b, normal));
+
+    match (feature_a, feature_b) {
+        (Feature::Edge(a0, a1), Feature::Edge(b0, b1)) => {
+            // The edge squarer to the normal is the reference face. Clip the
+            // other, incident, edge to its extent and keep the clipped ends
+            // that still reach past it.
+            let squareness = |start: Vec2, end: Vec2| (end - start).normalize().dot(normal).abs();
+            let (incident, reference, side, offset) = if squareness(b0, b1) <= squareness(a0, a1) {
+                ((a0, a1), (b0, b1), 1.0, -normal * radius_a)
+            } else {
+                ((b0, b1), (a0, a1), -1.0, normal * radius_b)
+            };
+
+            let tangent = normal.perp();
+            let (low, high) = {
+                let (t0, t1) = (reference.0.dot(tangent), reference.1.dot(tangent));
+                (t0.min(t1), t0.max(t1))
+            };
+            let (start, end) = incident;
+            let (s0, s1) = (start.dot(tangent), end.dot(tangent));
+            let clip = |target: f32| {
+                if (s1 - s0).abs() < f32::EPSILON {
+                    start
+                } else {
+                    start.lerp(end, ((target - s0) / (s1 - s0)).clamp(0.0, 1.0))
+                }
+            };
+            let face = (reference.0 + reference.1).dot(normal) / 2.0;
+
+            let mut manifold = Manifold {
+                normal,
+                points: [ContactPoint {
+                    position: start.lerp(end, 0.5) + offset,
+                    depth,
+                }; 2],
+                len: 0,
+            };
+            for point in [clip(low), clip(high)] {
+                let point_depth = radius - side * (point.dot(normal) - face);
+                if point_depth > 0.0 {
+                    manifold.points[manifold.len] = ContactPoint {
+                        position: point + offset,
+                        depth: point_depth,
+                    };
+                    manifold.len += 1;
+                }
+            }
+
+            // Keep the midpoint when rounding leaves neither end touching.
+            manifold.len = manifold.len.max(1);
+            manifold
+        }
+        (Feature::Vertex(vertex), _) => Manifold::single(
+            normal,
+            ContactPoint {
+                position: vertex - normal * radius_a,
+                depth,
+            },
+        ),
+        (_, Feature::Vertex(vertex)) => Manifold::single(
+            normal,
+            ContactPoint {
+                position: vertex + normal * radius_b,
+                depth,
+            },
+        ),
+    }
+}
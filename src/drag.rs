@@ -0,0 +1,25 @@
+use glam::Vec2;
+
+/// Velocity dependent deceleration, `-(linear + quadratic * |v|) * v`.
+///
+/// Coefficients act on acceleration directly rather than on force, so bodies
+/// of different mass slow down alike unless given their own [`Drag`]. The
+/// linear term models viscous damping and the quadratic term air resistance,
+/// giving a terminal speed of `g / linear` or `sqrt(g / quadratic)`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Drag {
+    pub linear: f32,
+    pub quadratic: f32,
+}
+
+impl Drag {
+    pub const NONE: Drag = Drag::new(0.0, 0.0);
+
+    pub const fn new(linear: f32, quadratic: f32) -> Self {
+        Self { linear, quadratic }
+    }
+
+    pub fn acceleration(&self, velocity: Vec2) -> Vec2 {
+        -(self.linear + self.quadratic * velocity.length()) * velocity
+    }
+}
@@ -0,0 +1,484 @@
+use crate::{
+    geometry::{area_and_centroid, edges, rotate, signed_area},
+    Aabb, Drag, Kinematic, Material, Path,
+};
+use glam::Vec2;
+use std::f32::consts::PI;
+
+#[cfg(feature = "render")]
+use macroquad::{color::Color, math, shapes};
+
+/// Density used to derive an entity's mass from its area.
+pub const DEFAULT_DENSITY: f32 = 1.0;
+
+/// Outline of an entity around its position, thickened by its radius.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// A disc of the entity's radius.
+    Circle,
+    /// A segment from `position - half_extent` to `position + half_extent`
+    /// at zero angle.
+    Capsule { half_extent: Vec2 },
+    /// A convex polygon with corners relative to its centroid at zero angle.
+    Polygon { vertices: Vec<Vec2> },
+}
+
+/// A rigid body: a circle, capsule or convex polygon.
+#[derive(Debug)]
+pub struct Entity {
+    pub radius: f32,
+    pub shape: Shape,
+    pub motion: Motion,
+    pub material: Material,
+    /// Overrides the resolver's drag for this entity when set.
+    pub drag: Option<Drag>,
+    /// Entities sharing a group pass through each other, like the links of a
+    /// rope that should not snag on itself.
+    pub group: Option<usize>,
+    /// Whether [`Entity::draw`] shows the entity, for particles that only
+    /// carry something drawn another way, like a cloth.
+    pub visible: bool,
+    /// Drives the entity along a scripted path when set, see
+    /// [`Entity::with_path`].
+    pub kinematic: Option<Kinematic>,
+    inverse_mass: f32,
+    inverse_inertia: f32,
+}
+
+impl Entity {
+    /// Creates an entity whose mass follows from its area at
+    /// [`DEFAULT_DENSITY`].
+    pub fn new(radius: f32, motion: Motion) -> Self {
+        Self::with_shape(radius, Shape::Circle, motion)
+    }
+
+    fn with_shape(radius: f32, shape: Shape, motion: Motion) -> Self {
+        Self {
+            radius,
+            shape,
+            motion,
+            material: Material::default(),
+            drag: None,
+            group: None,
+            visible: true,
+            kinematic: None,
+            inverse_mass: 0.0,
+            inverse_inertia: 0.0,
+        }
+        .with_density(DEFAULT_DENSITY)
+    }
+
+    /// Creates a capsule spanning `half_extent` either side of the motion's
+    /// position, with its mass derived like [`Entity::new`]. Give it an
+    /// infinite mass for a static ramp or wall made of entities.
+    pub fn capsule(radius: f32, half_extent: Vec2, motion: Motion) -> Self {
+        Self::with_shape(radius, Shape::Capsule { half_extent }, motion)
+    }
+
+    /// Creates a convex polygon from corners relative to the motion's
+    /// position, in either winding order. The corners are recentred on their
+    /// centroid, which moves the entity's position to match.
+    pub fn polygon(vertices: Vec<Vec2>, mut motion: Motion) -> Self {
+        assert!(
+            vertices.len() >= 3,
+            "a polygon needs at least three vertices"
+        );
+
+        let (area, centroid) = area_and_centroid(&vertices);
+        // Corners along one line have no centroid to recentre on.
+        assert!(area.abs() > f32::EPSILON, "a polygon needs a non-zero area");
+        let mut vertices: Vec<Vec2> = vertices.iter().map(|&v| v - centroid).collect();
+        if area < 0.0 {
+            vertices.reverse();
+        }
+        motion.position += centroid;
+        motion.previous_position += centroid;
+
+        Self::with_shape(0.0, Shape::Polygon { vertices }, motion)
+    }
+
+    /// Creates a box of `size` centred on the motion's position.
+    pub fn rectangle(size: Vec2, motion: Motion) -> Self {
+        let half = size / 2.0;
+        let vertices = vec![
+            Vec2::new(-half.x, -half.y),
+            Vec2::new(half.x, -half.y),
+            Vec2::new(half.x, half.y),
+            Vec2::new(-half.x, half.y),
+        ];
+
+        Self::polygon(vertices, motion)
+    }
+
+    /// Derives the mass from the area. A radius-less point has no area and
+    /// weighs as if it covered a unit of it.
+    pub fn with_density(self, density: f32) -> Self {
+        let area = self.area();
+        let mass = if area > 0.0 { area } else { 1.0 } * density;
+        self.with_mass(mass)
+    }
+
+    /// Sets the mass directly, scaling the moment of inertia with it. An
+    /// infinite mass makes the entity static: it is never moved by gravity,
+    /// integration or contacts.
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.set_mass(mass);
+        self
+    }
+
+    /// Fixes the entity where it is, for anchors and fixed obstacles.
+    pub fn pinned(self) -> Self {
+        self.with_mass(f32::INFINITY)
+    }
+
+    /// Moves the entity along `path` from the start of it, pushing what it
+    /// meets without being pushed back. See [`Kinematic`].
+    pub fn with_path(mut self, path: impl Path + 'static) -> Self {
+        let kinematic = Kinematic::new(path);
+        let (position, angle) = kinematic.pose();
+        self.motion.position = position;
+        self.motion.previous_position = position;
+        self.motion.angle = angle;
+        self.motion.previous_angle = angle;
+        self.kinematic = Some(kinematic);
+        self.pinned()
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    pub fn with_drag(mut self, drag: Drag) -> Self {
+        self.drag = Some(drag);
+        self
+    }
+
+    pub fn with_group(mut self, group: usize) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    pub fn hidden(mut self) -> Self {
+        self.visible = false;
+        self
+    }
+
+    /// Whether contacts between the two are resolved at all.
+    pub fn collides_with(&self, other: &Entity) -> bool {
+        let same_group = self.group.is_some() && self.group == other.group;
+        let both_static = self.is_static() && other.is_static();
+        !(same_group || both_static)
+    }
+
+    /// Sets the mass, which must be positive, or infinite for a static
+    /// entity.
+    pub fn set_mass(&mut self, mass: f32) {
+        assert!(
+            mass > 0.0,
+            "mass must be positive, or infinite for a static entity, not {}",
+            mass
+        );
+        let gyration = self.gyration_squared();
+
+        self.inverse_mass = mass.recip();
+        self.inverse_inertia = if gyration > 0.0 {
+            (mass * gyration).recip()
+        } else {
+            0.0
+        };
+    }
+
+    pub fn mass(&self) -> f32 {
+        self.inverse_mass.recip()
+    }
+
+    pub fn inverse_mass(&self) -> f32 {
+        self.inverse_mass
+    }
+
+    /// Moment of inertia about the centroid, infinite for static entities
+    /// and radius-less points.
+    pub fn inertia(&self) -> f32 {
+        self.inverse_inertia.recip()
+    }
+
+    pub fn inverse_inertia(&self) -> f32 {
+        self.inverse_inertia
+    }
+
+    /// Whether forces leave the entity alone: pinned and kinematic entities
+    /// both are.
+    pub fn is_static(&self) -> bool {
+        self.inverse_mass == 0.0
+    }
+
+    pub fn is_kinematic(&self) -> bool {
+        self.kinematic.is_some()
+    }
+
+    pub fn area(&self) -> f32 {
+        let round = PI * self.radius * self.radius;
+        match &self.shape {
+            Shape::Circle => round,
+            Shape::Capsule { half_extent } => round + 4.0 * self.radius * half_extent.length(),
+            Shape::Polygon { vertices } => signed_area(vertices).abs(),
+        }
+    }
+
+    /// Moment of inertia per unit mass about the centroid.
+    fn gyration_squared(&self) -> f32 {
+        match &self.shape {
+            Shape::Circle => self.radius * self.radius / 2.0,
+            Shape::Capsule { half_extent } => {
+                // A box between the caps, plus the two caps joined into a
+                // disc whose halves sit at either end.
+                let (length, radius) = (half_extent.length() * 2.0, self.radius);
+                let (slab, disc) = (length * radius * 2.0, PI * radius * radius);
+                let slab_inertia = slab * (length * length + 4.0 * radius * radius) / 12.0;
+                let disc_inertia = disc * (radius * radius / 2.0 + length * length / 4.0);
+                (slab_inertia + disc_inertia) / (slab + disc)
+            }
+            Shape::Polygon { vertices } => {
+                let (moment, weight) =
+                    edges(vertices).fold((0.0, 0.0), |(moment, weight), (a, b)| {
+                        let cross = a.perp_dot(b);
+                        (
+                            moment + cross * (a.dot(a) + a.dot(b) + b.dot(b)),
+                            weight + cross,
+                        )
+                    });
+                moment / (6.0 * weight)
+            }
+        }
+    }
+
+    /// Centre line of a circle or capsule, with both ends at the position for
+    /// a circle or polygon.
+    pub fn segment(&self) -> (Vec2, Vec2) {
+        let position = self.motion.position;
+        match self.shape {
+            Shape::Capsule { half_extent } => {
+                let half_extent = rotate(half_extent, self.motion.angle);
+                (position - half_extent, position + half_extent)
+            }
+            _ => (position, position),
+        }
+    }
+
+    /// Writes the corners of the shape in world space to `hull`. Thickened by
+    /// the radius they give the full outline: one point for a circle, two for
+    /// a capsule and every corner of a polygon.
+    pub fn hull(&self, hull: &mut Vec<Vec2>) {
+        hull.clear();
+        match &self.shape {
+            Shape::Polygon { vertices } => {
+                hull.extend(vertices.iter().map(|&v| self.world_point(v)))
+            }
+            _ => {
+                let (start, end) = self.segment();
+                hull.push(start);
+                if end != start {
+                    hull.push(end);
+                }
+            }
+        }
+    }
+
+    /// A point fixed to the body, given relative to its position at zero
+    /// angle, in world space.
+    pub fn world_point(&self, local: Vec2) -> Vec2 {
+        self.motion.position + rotate(local, self.motion.angle)
+    }
+
+    /// Velocity of the material point at `point`, including rotation.
+    pub fn velocity_at(&self, point: Vec2, dt: f32) -> Vec2 {
+        let arm = point - self.motion.position;
+        self.motion.velocity(dt) + arm.perp() * self.motion.angular_velocity(dt)
+    }
+
+    /// Changes the velocity of the body by pushing on it at `point`.
+    pub fn apply_impulse(&mut self, point: Vec2, impulse: Vec2, dt: f32) {
+        let arm = point - self.motion.position;
+        self.motion.add_velocity(impulse * self.inverse_mass, dt);
+        self.motion
+            .add_angular_velocity(arm.perp_dot(impulse) * self.inverse_inertia, dt);
+    }
+
+    /// Inverse of the mass felt when pushing at `point` along `normal`.
+    pub(crate) fn inverse_mass_at(&self, point: Vec2, normal: Vec2) -> f32 {
+        let lever = (point - self.motion.position).perp_dot(normal);
+        self.inverse_mass + lever * lever * self.inverse_inertia
+    }
+
+    /// Moves the body by pushing it at `point`, like [`Entity::apply_impulse`]
+    /// but for position.
+    pub(crate) fn displace(&mut self, point: Vec2, correction: Vec2) {
+        let arm = point - self.motion.position;
+        self.motion.position += correction * self.inverse_mass;
+        self.motion.angle += arm.perp_dot(correction) * self.inverse_inertia;
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        match &self.shape {
+            Shape::Polygon { vertices } => {
+                let position = self.motion.position;
+                let first = position + rotate(vertices[0], self.motion.angle);
+                vertices
+                    .iter()
+                    .map(|&v| position + rotate(v, self.motion.angle))
+                    .fold(Aabb::new(first, first), |bounds, v| {
+                        bounds.union(&Aabb::new(v, v))
+                    })
+                    .expand(self.radius)
+            }
+            _ => {
+                let (start, end) = self.segment();
+                Aabb::from_capsule(start, end, self.radius)
+            }
+        }
+    }
+
+    #[cfg(feature = "render")]
+    pub fn draw(&self, color: Color) {
+        if !self.visible {
+            return;
+        }
+        let (start, end) = self.segment();
+        match self.shape {
+            Shape::Circle => {
+                // A spoke from the centre to the rim shows the ball turning.
+                let rim = start + rotate(Vec2::new(self.radius, 0.0), self.motion.angle);
+                let spoke = Color::new(color.r * 0.5, color.g * 0.5, color.b * 0.5, color.a);
+                shapes::draw_poly(start.x, start.y, 100, self.radius, 0.0, color);
+                shapes::draw_line(start.x, start.y, rim.x, rim.y, 2.0, spoke);
+            }
+            Shape::Capsule { .. } => {
+                shapes::draw_line(start.x, start.y, end.x, end.y, self.radius * 2.0, color);
+                for cap in [start, end] {
+                    shapes::draw_poly(cap.x, cap.y, 100, self.radius, 0.0, color);
+                }
+            }
+            Shape::Polygon { .. } => {
+                let mut hull = Vec::new();
+                self.hull(&mut hull);
+                let point = |i: usize| math::vec2(hull[i].x, hull[i].y);
+                for i in 1..hull.len() - 1 {
+                    shapes::draw_triangle(point(0), point(i), point(i + 1), color);
+                }
+            }
+        }
+    }
+}
+
+/// Verlet state of an entity: velocity is implied by the difference between
+/// the current and previous position.
+#[derive(Debug)]
+pub struct Motion {
+    pub position: Vec2,
+    pub previous_position: Vec2,
+    pub acceleration: Vec2,
+    /// Orientation in radians, clockwise on screen.
+    pub angle: f32,
+    /// Orientation before the last step, implying the angular velocity the
+    /// same way `previous_position` implies the linear one.
+    pub previous_angle: f32,
+    /// Step length that produced `position` from `previous_position`, or `None`
+    /// before the first step.
+    pub previous_dt: Option<f32>,
+}
+
+impl Motion {
+    pub fn new(x: f32, y: f32) -> Self {
+        let position = Vec2::new(x, y);
+
+        Self {
+            position,
+            previous_position: position,
+            acceleration: Vec2::new(0.0, 0.0),
+            angle: 0.0,
+            previous_angle: 0.0,
+            previous_dt: None,
+        }
+    }
+
+    /// Time-corrected position Verlet step.
+    ///
+    /// The implied displacement is rescaled by `dt / previous_dt` and the
+    /// acceleration term by `dt * (dt + previous_dt) / 2`, which reduces to the
+    /// classic `a * dt²` for a constant step and stays exact for constant
+    /// acceleration when the step varies. The first step after creation or
+    /// [`Motion::set_velocity`] is a Taylor start with `a * dt² / 2`.
+    pub fn update_position(&mut self, dt: f32) {
+        let (ratio, previous_dt) = match self.previous_dt {
+            Some(previous_dt) => (dt / previous_dt, previous_dt),
+            None => (1.0, 0.0),
+        };
+        let displacement = (self.position - self.previous_position) * ratio;
+
+        self.previous_position = self.position;
+        self.position += displacement + self.acceleration * dt * (dt + previous_dt) * 0.5;
+        self.acceleration = Vec2::default();
+        self.previous_dt = Some(dt);
+    }
+
+    /// Advances the angle by the angular velocity over a step of `dt`. Run it
+    /// before the integrator, which records `dt` as the previous step.
+    pub fn rotate(&mut self, dt: f32) {
+        let angular_velocity = self.angular_velocity(dt);
+        self.previous_angle = self.angle;
+        self.angle += angular_velocity * dt;
+    }
+
+    /// Angular velocity over the last step in radians per second, falling
+    /// back to `dt` before the first one.
+    pub fn angular_velocity(&self, dt: f32) -> f32 {
+        (self.angle - self.previous_angle) / self.previous_dt.unwrap_or(dt)
+    }
+
+    /// Changes the angular velocity by `delta` without turning the body.
+    pub fn add_angular_velocity(&mut self, delta: f32, dt: f32) {
+        self.previous_angle -= delta * self.previous_dt.unwrap_or(dt);
+    }
+
+    pub fn set_angular_velocity(&mut self, angular_velocity: f32, dt: f32) {
+        self.previous_angle = self.angle - angular_velocity * self.previous_dt.unwrap_or(dt);
+    }
+
+    pub fn accelerate(&mut self, acceleration: Vec2) {
+        self.acceleration += acceleration;
+    }
+
+    /// Velocity over the last step, falling back to `dt` before the first one.
+    pub fn velocity(&self, dt: f32) -> Vec2 {
+        (self.position - self.previous_position) / self.previous_dt.unwrap_or(dt)
+    }
+
+    /// Finishes a step of length `dt` at `position` moving with `velocity`,
+    /// storing the velocity implicitly in `previous_position`.
+    pub fn advance(&mut self, position: Vec2, velocity: Vec2, dt: f32) {
+        self.position = position;
+        self.previous_position = position - velocity * dt;
+        self.acceleration = Vec2::default();
+        self.previous_dt = Some(dt);
+    }
+
+    /// Moves the body by `offset` without changing its velocity.
+    pub fn translate(&mut self, offset: Vec2) {
+        self.position += offset;
+        self.previous_position += offset;
+    }
+
+    /// Changes the velocity by `delta` without moving the body.
+    pub fn add_velocity(&mut self, delta: Vec2, dt: f32) {
+        self.previous_position -= delta * self.previous_dt.unwrap_or(dt);
+    }
+
+    /// Sets the velocity for a following step of length `dt`.
+    pub fn set_velocity(&mut self, velocity: Vec2, dt: f32) {
+        let angular_velocity = self.angular_velocity(dt);
+        self.previous_position = self.position - velocity * dt;
+        self.previous_angle = self.angle - angular_velocity * dt;
+        self.previous_dt = None;
+    }
+}
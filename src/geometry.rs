@@ -0,0 +1,88 @@
+//! Closest-point queries and polygon measures shared by the boundaries, the
+//! entities and the resolver.
+
+use glam::Vec2;
+
+/// Point on the segment `a`–`b` nearest to `point`.
+pub(crate) fn closest_on_segment(point: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared == 0.0 {
+        return a;
+    }
+
+    let t = ((point - a).dot(ab) / length_squared).clamp(0.0, 1.0);
+    a + ab * t
+}
+
+/// Nearest pair of points between the segments `a0`–`a1` and `b0`–`b1`, the
+/// first lying on `a` and the second on `b`. Either segment may be a point.
+pub(crate) fn closest_between_segments(a0: Vec2, a1: Vec2, b0: Vec2, b1: Vec2) -> (Vec2, Vec2) {
+    let (da, db) = (a1 - a0, b1 - b0);
+    let (la, lb) = (da.length_squared(), db.length_squared());
+
+    if la == 0.0 {
+        return (a0, closest_on_segment(a0, b0, b1));
+    }
+    if lb == 0.0 {
+        return (closest_on_segment(b0, a0, a1), b0);
+    }
+
+    // Parameters of the closest points on the infinite lines, clamped back
+    // onto the segments one after the other.
+    let r = a0 - b0;
+    let (b, c, f) = (da.dot(db), da.dot(r), db.dot(r));
+    let denominator = la * lb - b * b;
+
+    let mut s = if denominator > 0.0 {
+        ((b * f - c * lb) / denominator).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let mut t = (b * s + f) / lb;
+
+    if t < 0.0 {
+        t = 0.0;
+        s = (-c / la).clamp(0.0, 1.0);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = ((b - c) / la).clamp(0.0, 1.0);
+    }
+
+    (a0 + da * s, b0 + db * t)
+}
+
+/// `vector` rotated counter-clockwise by `angle` radians, or clockwise on
+/// screen where y points down.
+pub(crate) fn rotate(vector: Vec2, angle: f32) -> Vec2 {
+    let (sin, cos) = angle.sin_cos();
+    Vec2::new(
+        cos * vector.x - sin * vector.y,
+        sin * vector.x + cos * vector.y,
+    )
+}
+
+/// Edges of a closed polygon, the last running back to the first corner.
+pub(crate) fn edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// Signed area of a closed polygon, positive when its corners run
+/// counter-clockwise.
+pub(crate) fn signed_area(points: &[Vec2]) -> f32 {
+    edges(points).map(|(a, b)| a.perp_dot(b)).sum::<f32>() / 2.0
+}
+
+/// Signed area, as [`signed_area`], and centroid of a closed polygon.
+pub(crate) fn area_and_centroid(points: &[Vec2]) -> (f32, Vec2) {
+    let (twice_area, weighted) =
+        edges(points).fold((0.0, Vec2::ZERO), |(area, weighted), (a, b)| {
+            let cross = a.perp_dot(b);
+            (area + cross, weighted + (a + b) * cross)
+        });
+
+    (twice_area / 2.0, weighted / (3.0 * twice_area))
+}
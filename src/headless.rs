@@ -0,0 +1,141 @@
+use crate::World;
+use glam::Vec2;
+use std::{fmt, io};
+
+/// Steps a [`World`] a fixed number of times without touching any windowing
+/// or timing APIs, so simulations can run on machines without a GPU.
+#[derive(Debug, Clone, Copy)]
+pub struct Headless {
+    pub steps: usize,
+    pub dt: f32,
+}
+
+impl Headless {
+    pub fn new(steps: usize, dt: f32) -> Self {
+        Self { steps, dt }
+    }
+
+    pub fn run(&self, world: &mut World) -> Report {
+        (0..self.steps).for_each(|_| world.update(self.dt));
+
+        Report::new(world, self.steps, self.dt)
+    }
+}
+
+impl Default for Headless {
+    fn default() -> Self {
+        Self::new(600, 1.0 / 60.0)
+    }
+}
+
+/// Final state of a headless run.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub steps: usize,
+    pub dt: f32,
+    pub positions: Vec<Vec2>,
+    pub statistics: Statistics,
+}
+
+impl Report {
+    pub fn new(world: &World, steps: usize, dt: f32) -> Self {
+        let positions = world
+            .entities
+            .iter()
+            .map(|entity| entity.borrow().motion.position)
+            .collect();
+
+        Self {
+            steps,
+            dt,
+            positions,
+            statistics: Statistics::new(world, dt),
+        }
+    }
+
+    /// Writes one `index,x,y` row per entity, preceded by a header.
+    pub fn write_positions(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writeln!(writer, "index,x,y")?;
+        for (i, position) in self.positions.iter().enumerate() {
+            writeln!(writer, "{},{},{}", i, position.x, position.y)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "steps: {}", self.steps)?;
+        writeln!(f, "dt: {}", self.dt)?;
+        writeln!(f, "simulated time: {}", self.steps as f32 * self.dt)?;
+        write!(f, "{}", self.statistics)
+    }
+}
+
+/// Aggregate measurements over every entity in a world.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Statistics {
+    pub entity_count: usize,
+    pub centroid: Vec2,
+    pub min: Vec2,
+    pub max: Vec2,
+    pub mean_speed: f32,
+    pub max_speed: f32,
+    /// Kinetic energy summed over every entity with finite mass.
+    pub kinetic_energy: f32,
+}
+
+impl Statistics {
+    pub fn new(world: &World, dt: f32) -> Self {
+        let entity_count = world.entities.len();
+        if entity_count == 0 {
+            return Self::default();
+        }
+
+        let mut statistics = Self {
+            entity_count,
+            min: Vec2::splat(f32::INFINITY),
+            max: Vec2::splat(f32::NEG_INFINITY),
+            ..Self::default()
+        };
+        let mut total_speed = 0.0;
+
+        world
+            .entities
+            .iter()
+            .map(|entity| entity.borrow())
+            .for_each(|entity| {
+                let motion = &entity.motion;
+                let speed = motion.velocity(dt).length();
+
+                statistics.centroid += motion.position;
+                statistics.min = statistics.min.min(motion.position);
+                statistics.max = statistics.max.max(motion.position);
+                statistics.max_speed = statistics.max_speed.max(speed);
+                if !entity.is_static() {
+                    statistics.kinetic_energy += 0.5 * entity.mass() * speed * speed;
+                }
+                total_speed += speed;
+            });
+
+        statistics.centroid /= entity_count as f32;
+        statistics.mean_speed = total_speed / entity_count as f32;
+        statistics
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "entities: {}", self.entity_count)?;
+        writeln!(f, "centroid: ({}, {})", self.centroid.x, self.centroid.y)?;
+        writeln!(
+            f,
+            "bounds: ({}, {}) - ({}, {})",
+            self.min.x, self.min.y, self.max.x, self.max.y
+        )?;
+        writeln!(f, "mean speed: {}", self.mean_speed)?;
+        writeln!(f, "max speed: {}", self.max_speed)?;
+        writeln!(f, "kinetic energy: {}", self.kinetic_energy)
+    }
+}
@@ -0,0 +1,100 @@
+use crate::Motion;
+use glam::Vec2;
+use std::fmt;
+
+/// State dependent acceleration, evaluated from a position and velocity on
+/// top of whatever has been accumulated in [`Motion::acceleration`].
+pub type Acceleration<'a> = &'a dyn Fn(Vec2, Vec2) -> Vec2;
+
+/// A scheme for advancing a [`Motion`] by one step.
+///
+/// Every scheme reads the current velocity from the Verlet state, so the
+/// positional corrections made by constraints and collisions feed back into
+/// the velocity the same way regardless of which integrator is in use.
+pub trait Integrator: fmt::Debug {
+    fn name(&self) -> &'static str;
+
+    fn integrate(&self, motion: &mut Motion, acceleration: Acceleration, dt: f32);
+}
+
+/// Explicit (forward) Euler: position is advanced with the old velocity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Euler;
+
+impl Integrator for Euler {
+    fn name(&self) -> &'static str {
+        "euler"
+    }
+
+    fn integrate(&self, motion: &mut Motion, acceleration: Acceleration, dt: f32) {
+        let velocity = motion.velocity(dt);
+        let a = motion.acceleration + acceleration(motion.position, velocity);
+
+        motion.advance(motion.position + velocity * dt, velocity + a * dt, dt);
+    }
+}
+
+/// Semi-implicit (symplectic) Euler: position is advanced with the new
+/// velocity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SemiImplicitEuler;
+
+impl Integrator for SemiImplicitEuler {
+    fn name(&self) -> &'static str {
+        "semi-implicit euler"
+    }
+
+    fn integrate(&self, motion: &mut Motion, acceleration: Acceleration, dt: f32) {
+        let velocity = motion.velocity(dt);
+        let velocity =
+            velocity + (motion.acceleration + acceleration(motion.position, velocity)) * dt;
+
+        motion.advance(motion.position + velocity * dt, velocity, dt);
+    }
+}
+
+/// Time-corrected position Verlet, see [`Motion::update_position`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Verlet;
+
+impl Integrator for Verlet {
+    fn name(&self) -> &'static str {
+        "verlet"
+    }
+
+    fn integrate(&self, motion: &mut Motion, acceleration: Acceleration, dt: f32) {
+        let a = acceleration(motion.position, motion.velocity(dt));
+
+        motion.accelerate(a);
+        motion.update_position(dt);
+    }
+}
+
+/// Classic fourth order Runge-Kutta.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rk4;
+
+impl Integrator for Rk4 {
+    fn name(&self) -> &'static str {
+        "rk4"
+    }
+
+    fn integrate(&self, motion: &mut Motion, acceleration: Acceleration, dt: f32) {
+        let base = motion.acceleration;
+        let derivative =
+            |position: Vec2, velocity: Vec2| (velocity, base + acceleration(position, velocity));
+
+        let x = motion.position;
+        let v = motion.velocity(dt);
+        let (k1x, k1v) = derivative(x, v);
+        let (k2x, k2v) = derivative(x + k1x * dt * 0.5, v + k1v * dt * 0.5);
+        let (k3x, k3v) = derivative(x + k2x * dt * 0.5, v + k2v * dt * 0.5);
+        let (k4x, k4v) = derivative(x + k3x * dt, v + k3v * dt);
+
+        motion.advance(
+            x + (k1x + 2.0 * k2x + 2.0 * k3x + k4x) * dt / 6.0,
+            v + (k1v + 2.0 * k2v + 2.0 * k3v + k4v) * dt / 6.0,
+            dt,
+        );
+    }
+}
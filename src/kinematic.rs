@@ -0,0 +1,60 @@
+use crate::Motion;
+use glam::Vec2;
+use std::fmt;
+
+/// A scripted motion: where an entity is and how it is turned at each moment.
+///
+/// Any `Fn(f32) -> (Vec2, f32)` is a path, taking the seconds since the
+/// entity set off and returning its position and angle.
+pub trait Path {
+    fn pose(&self, time: f32) -> (Vec2, f32);
+}
+
+impl<F: Fn(f32) -> (Vec2, f32)> Path for F {
+    fn pose(&self, time: f32) -> (Vec2, f32) {
+        self(time)
+    }
+}
+
+/// Drives an entity along a [`Path`] instead of letting forces move it.
+///
+/// Kinematic entities have an infinite mass, so contacts and sticks never
+/// push them back, but the velocity implied by their path carries into what
+/// they hit, like a paddle swatting a ball.
+pub struct Kinematic {
+    path: Box<dyn Path>,
+    /// Seconds along the path.
+    pub time: f32,
+}
+
+impl Kinematic {
+    pub fn new(path: impl Path + 'static) -> Self {
+        Self {
+            path: Box::new(path),
+            time: 0.0,
+        }
+    }
+
+    /// Position and angle on the path at the current time.
+    pub fn pose(&self) -> (Vec2, f32) {
+        self.path.pose(self.time)
+    }
+
+    /// Moves `motion` to where the path is `dt` later, keeping where it was
+    /// so the step implies a velocity.
+    pub(crate) fn advance(&mut self, motion: &mut Motion, dt: f32) {
+        self.time += dt;
+        let (position, angle) = self.pose();
+        motion.advance(position, (position - motion.position) / dt, dt);
+        motion.previous_angle = motion.angle;
+        motion.angle = angle;
+    }
+}
+
+impl fmt::Debug for Kinematic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Kinematic")
+            .field("time", &self.time)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,49 @@
+//! A small position-based physics engine built around Verlet integration.
+//!
+//! The core types carry no dependency on a windowing layer. Drawing helpers
+//! are available behind the `render` feature, which pulls in macroquad.
+
+mod aabb;
+mod blob;
+mod boundary;
+mod broadphase;
+mod cloth;
+mod cluster;
+mod collision;
+mod drag;
+mod entity;
+mod geometry;
+mod headless;
+mod integrator;
+mod kinematic;
+mod material;
+mod periodic;
+mod pressure;
+mod resolver;
+mod rope;
+mod stick;
+mod timestep;
+mod world;
+
+pub use aabb::Aabb;
+pub use blob::Blob;
+pub use boundary::{
+    Boundary, Circle, Contact, Inverted, Moving, Polygon, Projection, Rectangle, Segment, Transform,
+};
+pub use broadphase::{Axis, Broadphase, BruteForce, Bvh, SweepAndPrune, UniformGrid};
+pub use cloth::Cloth;
+pub use cluster::Cluster;
+pub use drag::Drag;
+pub use entity::{Entity, Motion, Shape, DEFAULT_DENSITY};
+pub use glam::Vec2;
+pub use headless::{Headless, Report, Statistics};
+pub use integrator::{Acceleration, Euler, Integrator, Rk4, SemiImplicitEuler, Verlet};
+pub use kinematic::{Kinematic, Path};
+pub use material::{CombineRule, Material};
+pub use periodic::Periodic;
+pub use pressure::Pressure;
+pub use resolver::Resolver;
+pub use rope::Rope;
+pub use stick::Stick;
+pub use timestep::FixedTimestep;
+pub use world::World;
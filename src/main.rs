@@ -1,15 +1,56 @@
+use glam::Vec4;
+use simple_physics_engine::{
+    Blob, Boundary, Broadphase, BruteForce, Bvh, Circle, Cloth, Cluster, Drag, Entity, Euler,
+    Headless, Integrator, Inverted, Material, Motion, Moving, Periodic, Polygon, Pressure,
+    Rectangle, Resolver, Rk4, Rope, Segment, SemiImplicitEuler, Stick, SweepAndPrune, Transform,
+    UniformGrid, Vec2, Verlet, World,
+};
+use std::{env, fs::File, io, ops::Range, process, str::FromStr};
+
+#[cfg(feature = "render")]
 use macroquad::{
     color::{colors, Color},
     input::{self, KeyCode},
-    main,
-    math::{Vec2, Vec4},
-    rand, shapes, time,
+    text, time,
     window::{self, Conf},
+    Window,
 };
-use std::cell::RefCell;
+#[cfg(feature = "render")]
+use simple_physics_engine::FixedTimestep;
 
 const ENTITY_COUNT: usize = 100;
-
+const TIMESTEP: f32 = 1.0 / 60.0;
+const SUBSTEPS: usize = 4;
+#[cfg(feature = "render")]
+const MAX_STEPS_PER_FRAME: usize = 5;
+
+const USAGE: &str = "\
+usage: simple-physics-engine [--boundary <name>]
+       simple-physics-engine headless [options]
+
+Runs the interactive demo when no subcommand is given, which needs the
+`render` feature. The headless runner works without it.
+
+headless options:
+    --steps <n>      number of steps to simulate (default 600)
+    --dt <seconds>   duration of each step (default 0.016666)
+    --count <n>      number of entities to spawn (default 100)
+    --seed <n>       seed for entity placement (default 0)
+    --radius <r>     entity radius: a fixed value `r`, uniform `min..max` or
+                     log-uniform `log:min..max` (default 15..30)
+    --integrator <name>
+                     one of euler, semi-implicit-euler, verlet or rk4 (default verlet)
+    --broadphase <name>
+                     one of brute-force, grid, sweep-and-prune or bvh (default grid)
+    --boundary <name>
+                     one of circle, box, hexagon, star, galton, hourglass,
+                     ramps, crates, pendulums, ropes, cloth, blobs, jelly,
+                     paddles, drum, shaker or periodic (default circle)
+    --drag <linear>,<quadratic>
+                     drag coefficients applied to every entity (default 0,0)
+    --output <path>  write final positions as csv to a file instead of stdout";
+
+#[cfg(feature = "render")]
 fn config() -> Conf {
     Conf {
         window_title: "Balls".to_owned(),
@@ -18,287 +59,851 @@ fn config() -> Conf {
     }
 }
 
-#[main(config)]
-async fn main() {
-    let mut app = App::new(
-        colors::BLACK,
-        Constraint::default(),
-        Vec4::new(600.0, 300.0, 200.0, 200.0),
-        Resolver::default(),
-        25.0,
-        colors::WHITE,
-        ENTITY_COUNT,
-    );
+fn integrator(name: &str) -> Option<Box<dyn Integrator>> {
+    match name {
+        "euler" => Some(Box::new(Euler)),
+        "semi-implicit-euler" => Some(Box::new(SemiImplicitEuler)),
+        "verlet" => Some(Box::new(Verlet)),
+        "rk4" => Some(Box::new(Rk4)),
+        _ => None,
+    }
+}
 
-    app.run().await
+fn broadphase(name: &str) -> Option<Box<dyn Broadphase>> {
+    match name {
+        "brute-force" => Some(Box::new(BruteForce)),
+        "grid" => Some(Box::new(UniformGrid::new())),
+        "sweep-and-prune" => Some(Box::new(SweepAndPrune::default())),
+        "bvh" => Some(Box::new(Bvh::default())),
+        _ => None,
+    }
 }
 
-struct App {
-    background_color: Color,
-    entities: Vec<RefCell<Entity>>,
-    border: Constraint,
-    resolver: Resolver,
+/// Entities held by a cluster and the settings it is given once they are
+/// placed.
+type ClusterLayout = (Range<usize>, fn(Cluster) -> Cluster);
+
+/// The static colliders of a demo, or the domain it wraps around, any bodies
+/// placed by hand, the sticks, pressures and clusters between them and where
+/// the generated entities start.
+struct Scene {
+    boundaries: Vec<Box<dyn Boundary>>,
+    periodic: Option<Periodic>,
+    entities: Vec<Entity>,
+    sticks: Vec<Stick>,
+    pressures: Vec<Pressure>,
+    /// Clusters are laid out from the entities once they are in the world.
+    clusters: Vec<ClusterLayout>,
+    spawn_area: Vec4,
 }
 
-impl App {
-    pub fn new(
-        background_color: Color,
-        border: Constraint,
-        spawn_area: Vec4,
-        resolver: Resolver,
-        entity_radius: f32,
-        entity_color: Color,
-        entity_count: usize,
-    ) -> Self {
-        let entities = Self::generate_entities(
-            Vec2::new(spawn_area.x, spawn_area.y),
-            Vec2::new(spawn_area.z, spawn_area.w),
-            entity_radius,
-            entity_color,
-            entity_count,
-        )
-        .into_iter()
-        .map(|entity| RefCell::new(entity))
-        .collect();
+impl Scene {
+    fn new(boundary: impl Boundary + 'static) -> Self {
+        Self {
+            boundaries: vec![Box::new(boundary)],
+            periodic: None,
+            entities: Vec::new(),
+            sticks: Vec::new(),
+            pressures: Vec::new(),
+            clusters: Vec::new(),
+            spawn_area: Vec4::new(600.0, 300.0, 200.0, 200.0),
+        }
+    }
 
+    /// A scene without walls wrapping around `periodic`. Nothing would stop
+    /// entities falling through it forever, so it turns gravity off.
+    fn periodic(periodic: Periodic) -> Self {
         Self {
-            background_color,
-            entities,
-            border,
-            resolver,
+            boundaries: Vec::new(),
+            periodic: Some(periodic),
+            ..Self::new(Circle::default())
         }
     }
 
-    pub async fn run(&mut self) {
-        // let mut ball = Entity::new(
-        //     25.0,
-        //     colors::WHITE,
-        //     Motion::new(
-        //         self.border.position.x - self.border.radius + 25.0,
-        //         self.border.position.y,
-        //     ),
-        // );
+    fn with(mut self, boundary: impl Boundary + 'static) -> Self {
+        self.boundaries.push(Box::new(boundary));
+        self
+    }
 
-        loop {
-            if input::is_key_released(KeyCode::Escape) {
-                break;
-            };
+    fn with_entity(mut self, entity: Entity) -> Self {
+        self.entities.push(entity);
+        self
+    }
 
-            self.tick().await
-        }
+    fn with_stick(mut self, stick: Stick) -> Self {
+        self.sticks.push(stick);
+        self
     }
 
-    async fn tick(&mut self) {
-        let dt = time::get_frame_time();
+    fn with_rope(mut self, rope: Rope) -> Self {
+        let (entities, sticks) = rope.build(self.entities.len());
+        self.entities.extend(entities);
+        self.sticks.extend(sticks);
+        self
+    }
 
-        window::clear_background(self.background_color);
+    fn with_cloth(mut self, cloth: Cloth) -> Self {
+        let (entities, sticks) = cloth.build(self.entities.len());
+        self.entities.extend(entities);
+        self.sticks.extend(sticks);
+        self
+    }
 
-        self.update(dt);
-        self.draw();
+    fn with_blob(mut self, blob: Blob) -> Self {
+        let (entities, sticks, pressure) = blob.build(self.entities.len());
+        self.entities.extend(entities);
+        self.sticks.extend(sticks);
+        self.pressures.push(pressure);
+        self
+    }
 
-        window::next_frame().await
+    fn with_cluster(mut self, entities: Vec<Entity>, configure: fn(Cluster) -> Cluster) -> Self {
+        let first = self.entities.len();
+        self.entities.extend(entities);
+        self.clusters.push((first..self.entities.len(), configure));
+        self
     }
 
-    fn update(&mut self, dt: f32) {
-        self.resolver.update(&self.entities, &self.border, dt);
+    fn spawning_in(mut self, spawn_area: Vec4) -> Self {
+        self.spawn_area = spawn_area;
+        self
     }
 
-    fn draw(&self) {
-        self.border.draw();
-        self.entities
-            .iter()
-            .for_each(|entity| entity.borrow().draw());
+    fn into_world(self, mut resolver: Resolver) -> World {
+        if let Some(periodic) = self.periodic {
+            resolver.gravity = Vec2::ZERO;
+            resolver.periodic = Some(periodic);
+        }
+        let mut world = World {
+            entities: Vec::new(),
+            boundaries: self.boundaries,
+            sticks: self.sticks,
+            pressures: self.pressures,
+            clusters: Vec::new(),
+            resolver,
+        }
+        .with_entities(self.entities);
+        for (particles, configure) in self.clusters {
+            let cluster = configure(Cluster::new(&world.entities, particles));
+            world.add_cluster(cluster);
+        }
+        world
     }
+}
 
-    fn generate_entities(
-        position: Vec2,
-        dimensions: Vec2,
-        radius: f32,
-        color: Color,
-        n: usize,
-    ) -> Vec<Entity> {
-        (0..n)
-            .map(|_| {
-                let x = rand::gen_range(position.x, position.x + dimensions.x);
-                let y = rand::gen_range(position.y, position.y + dimensions.y);
-
-                Entity::new(radius, color, Motion::new(x, y))
-            })
-            .collect()
+fn scene(name: &str) -> Option<Scene> {
+    let center = Vec2::new(800.0, 450.0);
+
+    match name {
+        "circle" => Some(Scene::new(Circle::default())),
+        "box" => Some(Scene::new(Rectangle::from_center(
+            center,
+            Vec2::new(800.0, 800.0),
+        ))),
+        "hexagon" => Some(Scene::new(Polygon::regular(center, 420.0, 6))),
+        "star" => Some(Scene::new(Polygon::star(center, 440.0, 260.0, 5))),
+        "galton" => Some(galton(center)),
+        "hourglass" => Some(hourglass(center)),
+        "ramps" => Some(ramps(center)),
+        "crates" => Some(crates(center)),
+        "pendulums" => Some(pendulums(center)),
+        "ropes" => Some(ropes(center)),
+        "cloth" => Some(cloth(center)),
+        "blobs" => Some(blobs(center)),
+        "jelly" => Some(jelly(center)),
+        "paddles" => Some(paddles(center)),
+        "drum" => Some(drum(center)),
+        "shaker" => Some(shaker(center)),
+        "periodic" => Some(periodic(center)),
+        _ => None,
     }
 }
 
-#[derive(Debug)]
-struct Entity {
-    radius: f32,
-    color: Color,
-    motion: Motion,
+/// A box with a funnel feeding rows of staggered pegs that drop into bins.
+fn galton(center: Vec2) -> Scene {
+    let size = Vec2::new(800.0, 800.0);
+    let (min, max) = (center - size / 2.0, center + size / 2.0);
+    let spacing = 80.0;
+
+    let scene = Scene::new(Rectangle::from_center(center, size))
+        .with(Segment::new(
+            Vec2::new(min.x, min.y + 80.0),
+            Vec2::new(center.x - 40.0, min.y + 210.0),
+            4.0,
+        ))
+        .with(Segment::new(
+            Vec2::new(max.x, min.y + 80.0),
+            Vec2::new(center.x + 40.0, min.y + 210.0),
+            4.0,
+        ))
+        .spawning_in(Vec4::new(min.x + 100.0, min.y + 10.0, size.x - 200.0, 60.0));
+
+    let pegs = (0..6).flat_map(|row| {
+        let offset = if row % 2 == 0 { 0.0 } else { spacing / 2.0 };
+        let y = min.y + 290.0 + row as f32 * 60.0;
+        (-5..5).map(move |column| Vec2::new(center.x + offset + column as f32 * spacing, y))
+    });
+    let bins = (1..10).map(|bin| min.x + bin as f32 * spacing);
+
+    let scene = pegs.fold(scene, |scene, peg| {
+        scene.with(Inverted(Circle::new(peg, 6.0)))
+    });
+    bins.fold(scene, |scene, x| {
+        scene.with(Segment::new(
+            Vec2::new(x, max.y - 150.0),
+            Vec2::new(x, max.y),
+            3.0,
+        ))
+    })
 }
 
-impl Entity {
-    pub fn new(radius: f32, color: Color, motion: Motion) -> Self {
-        Self {
-            radius,
-            color,
-            motion,
-        }
-    }
+/// Zigzag ramps down a box, a one-way platform the balls land on and a plank
+/// that tumbles along with them.
+fn ramps(center: Vec2) -> Scene {
+    let size = Vec2::new(800.0, 800.0);
+    let (min, max) = (center - size / 2.0, center + size / 2.0);
+    let ramp = |from: Vec2, to: Vec2| Segment::new(center + from, center + to, 5.0);
+
+    Scene::new(Rectangle::from_center(center, size))
+        .with(ramp(Vec2::new(-400.0, -240.0), Vec2::new(200.0, -140.0)))
+        .with(ramp(Vec2::new(400.0, -20.0), Vec2::new(-200.0, 80.0)))
+        .with(
+            Segment::new(
+                Vec2::new(min.x, max.y - 120.0),
+                Vec2::new(center.x, max.y - 120.0),
+                3.0,
+            )
+            .one_way(),
+        )
+        .with_entity(Entity::capsule(
+            12.0,
+            Vec2::new(60.0, 0.0),
+            Motion::new(center.x + 200.0, center.y - 320.0),
+        ))
+        .with_entity(
+            Entity::capsule(
+                8.0,
+                Vec2::new(0.0, 50.0),
+                Motion::new(center.x + 200.0, max.y - 60.0),
+            )
+            .pinned(),
+        )
+        .spawning_in(Vec4::new(min.x + 20.0, min.y + 20.0, 300.0, 100.0))
+}
 
-    pub fn draw(&self) {
-        shapes::draw_poly(
-            self.motion.position.x,
-            self.motion.position.y,
-            100,
-            self.radius,
-            0.0,
-            self.color,
+/// A pyramid of crates beside a tower of offset ones and a few loose
+/// triangles, for balls to knock over.
+fn crates(center: Vec2) -> Scene {
+    let size = Vec2::new(800.0, 800.0);
+    let (min, max) = (center - size / 2.0, center + size / 2.0);
+    let side = 50.0;
+
+    let pyramid = (0..5).flat_map(|row| {
+        (0..5 - row).map(move |column| {
+            let x = min.x + 120.0 + (column as f32 + row as f32 / 2.0) * (side + 2.0);
+            Motion::new(x, max.y - side / 2.0 - row as f32 * (side + 1.0))
+        })
+    });
+    let tower = (0..6).map(|row| {
+        let x = max.x - 150.0 + if row % 2 == 0 { 0.0 } else { 10.0 };
+        Motion::new(x, max.y - side / 2.0 - row as f32 * (side + 1.0))
+    });
+    let triangles = (0..3).map(|i| {
+        Entity::polygon(
+            vec![
+                Vec2::new(0.0, -30.0),
+                Vec2::new(30.0, 20.0),
+                Vec2::new(-30.0, 20.0),
+            ],
+            Motion::new(center.x - 60.0 + i as f32 * 70.0, center.y),
         )
-    }
+    });
+
+    pyramid
+        .chain(tower)
+        .map(|motion| Entity::rectangle(Vec2::new(side, side), motion))
+        .chain(triangles)
+        .fold(
+            Scene::new(Rectangle::from_center(center, size)),
+            |scene, entity| scene.with_entity(entity),
+        )
+        .spawning_in(Vec4::new(min.x + 20.0, min.y + 20.0, size.x - 40.0, 100.0))
 }
 
-#[derive(Debug)]
-struct Motion {
-    position: Vec2,
-    previous_position: Vec2,
-    acceleration: Vec2,
+/// A pendulum, a double pendulum, a ball on an elastic stick and a crate
+/// hung by one corner, all from static pivots along the top.
+fn pendulums(center: Vec2) -> Scene {
+    let size = Vec2::new(800.0, 800.0);
+    let (min, max) = (center - size / 2.0, center + size / 2.0);
+    let top = min.y + 80.0;
+    let pivot = |x: f32| Entity::new(6.0, Motion::new(min.x + x, top)).pinned();
+    let ball = |x: f32, y: f32| Entity::new(20.0, Motion::new(min.x + x, top + y));
+
+    Scene::new(Rectangle::from_center(center, size))
+        .with_entity(pivot(150.0))
+        .with_entity(ball(300.0, 0.0))
+        .with_stick(Stick::new(0, 1, 150.0))
+        .with_entity(pivot(380.0))
+        .with_entity(ball(380.0, -90.0))
+        .with_entity(ball(460.0, -150.0))
+        .with_stick(Stick::new(2, 3, 90.0))
+        .with_stick(Stick::new(3, 4, 100.0))
+        .with_entity(pivot(560.0))
+        .with_entity(ball(560.0, 250.0))
+        .with_stick(Stick::new(5, 6, 120.0).with_stiffness(20000.0))
+        .with_entity(pivot(690.0))
+        .with_entity(Entity::rectangle(
+            Vec2::new(60.0, 40.0),
+            Motion::new(min.x + 750.0, top + 60.0),
+        ))
+        .with_stick(Stick::new(7, 8, 40.0).with_anchors(Vec2::ZERO, Vec2::new(-30.0, -20.0)))
+        .spawning_in(Vec4::new(min.x + 20.0, max.y - 200.0, size.x - 40.0, 100.0))
 }
 
-impl Motion {
-    pub fn new(x: f32, y: f32) -> Self {
-        let position = Vec2::new(x, y);
+/// A sagging bridge for balls to land on, a rope hanging from the ceiling
+/// and a loose chain dropped across the bridge.
+fn ropes(center: Vec2) -> Scene {
+    let size = Vec2::new(800.0, 800.0);
+    let (min, max) = (center - size / 2.0, center + size / 2.0);
+    let bridge = center.y - 100.0;
+
+    Scene::new(Rectangle::from_center(center, size))
+        .with_rope(
+            Rope::new(Vec2::new(min.x, bridge), Vec2::new(max.x, bridge), 40)
+                .with_radius(8.0)
+                .with_length(size.x * 1.05)
+                .pin_start()
+                .pin_end(),
+        )
+        .with_rope(
+            Rope::new(
+                Vec2::new(max.x - 120.0, min.y),
+                Vec2::new(max.x - 120.0, min.y + 300.0),
+                30,
+            )
+            .with_radius(4.0)
+            .pin_start()
+            .self_colliding(),
+        )
+        .with_rope(
+            Rope::new(
+                Vec2::new(min.x + 150.0, center.y - 300.0),
+                Vec2::new(min.x + 450.0, center.y - 340.0),
+                20,
+            )
+            .with_radius(6.0),
+        )
+        .spawning_in(Vec4::new(min.x + 100.0, min.y + 20.0, 400.0, 100.0))
+}
 
-        Self {
-            position,
-            previous_position: position,
-            acceleration: Vec2::new(0.0, 0.0),
+/// A sheet of cloth hung by its top corners that tears under falling balls.
+fn cloth(center: Vec2) -> Scene {
+    let size = Vec2::new(800.0, 800.0);
+    let min = center - size / 2.0;
+
+    Scene::new(Rectangle::from_center(center, size))
+        .with_cloth(
+            Cloth::new(
+                Vec2::new(center.x - 300.0, min.y + 150.0),
+                Vec2::new(600.0, 360.0),
+                41,
+                25,
+            )
+            .with_tearing(2.0)
+            .pin_top_corners(),
+        )
+        .spawning_in(Vec4::new(center.x - 250.0, min.y + 20.0, 500.0, 100.0))
+}
+
+/// Soft blobs of different sizes and firmness tumbling with the balls in the
+/// default circle.
+fn blobs(center: Vec2) -> Scene {
+    let blob = |x: f32, y: f32, radius: f32| Blob::new(center + Vec2::new(x, y), radius, 24);
+
+    Scene::new(Circle::default())
+        .with_blob(blob(-150.0, -150.0, 80.0))
+        .with_blob(blob(150.0, -200.0, 60.0).with_pressure_stiffness(20.0))
+        .with_blob(blob(0.0, 100.0, 100.0).with_inflation(0.7))
+        .with_blob(
+            blob(200.0, 100.0, 50.0)
+                .with_rim_stiffness(2e4)
+                .with_inflation(1.3),
+        )
+        .spawning_in(Vec4::new(center.x - 200.0, center.y - 380.0, 400.0, 100.0))
+}
+
+/// Scripted bodies that push balls around without being pushed back: two
+/// paddles turning opposite ways, a platform sliding to and fro and a
+/// pendulum whose anchor sways.
+fn paddles(center: Vec2) -> Scene {
+    let size = Vec2::new(800.0, 800.0);
+    let (min, max) = (center - size / 2.0, center + size / 2.0);
+    let paddle = |x: f32, speed: f32| {
+        let hub = Vec2::new(center.x + x, center.y - 80.0);
+        Entity::capsule(10.0, Vec2::new(90.0, 0.0), Motion::new(hub.x, hub.y))
+            .with_path(move |time: f32| (hub, time * speed))
+    };
+    let platform = Entity::rectangle(Vec2::new(200.0, 20.0), Motion::new(0.0, 0.0)).with_path(
+        move |time: f32| {
+            let x = center.x + (time * 0.8).sin() * 250.0;
+            (Vec2::new(x, center.y + 200.0), 0.0)
+        },
+    );
+    let anchor = Entity::new(6.0, Motion::new(0.0, 0.0)).with_path(move |time: f32| {
+        let x = center.x + (time * 1.5).sin() * 150.0;
+        (Vec2::new(x, min.y + 40.0), 0.0)
+    });
+
+    Scene::new(Rectangle::from_center(center, size))
+        .with_entity(paddle(-180.0, 1.5))
+        .with_entity(paddle(180.0, -1.5))
+        .with_entity(platform)
+        .with_entity(anchor)
+        .with_entity(Entity::new(25.0, Motion::new(center.x, min.y + 200.0)))
+        .with_stick(Stick::new(3, 4, 160.0))
+        .spawning_in(Vec4::new(min.x + 20.0, max.y - 250.0, size.x - 40.0, 100.0))
+}
+
+/// A tumbler: an octagonal drum turning slowly, with vanes along its wall
+/// that lift the balls and drop them across the middle.
+fn drum(center: Vec2) -> Scene {
+    let (radius, speed) = (400.0, 0.8);
+    let wall = Polygon::regular(center, radius, 8).with_material(Material::new(0.2, 0.8));
+    let vane = |angle: f32| {
+        let direction = Vec2::new(angle.cos(), angle.sin());
+        let vane = Segment::new(
+            center + direction * (radius - 10.0),
+            center + direction * (radius - 130.0),
+            8.0,
+        );
+        Moving::spinning(vane, center, speed)
+    };
+
+    let scene = Scene::new(Moving::spinning(wall, center, speed));
+    (0..4)
+        .map(|i| vane(i as f32 * std::f32::consts::FRAC_PI_2))
+        .fold(scene, Scene::with)
+        .spawning_in(Vec4::new(center.x - 150.0, center.y - 150.0, 300.0, 300.0))
+}
+
+/// A box shaken from side to side and breathing in and out, stirring up the
+/// balls inside.
+fn shaker(center: Vec2) -> Scene {
+    let walls = Rectangle::from_center(center, Vec2::new(500.0, 700.0));
+    Scene::new(Moving::new(walls, center, |time: f32| Transform {
+        offset: Vec2::new((time * 9.0).sin() * 40.0, 0.0),
+        angle: (time * 0.7).sin() * 0.3,
+        scale: 1.0 + (time * 2.0).sin() * 0.1,
+    }))
+    .spawning_in(Vec4::new(center.x - 200.0, center.y, 400.0, 300.0))
+}
+
+/// A patch of an endless gas: balls fill a domain that wraps around at every
+/// edge, stirred by a few heavy, fast and bouncy ones.
+fn periodic(center: Vec2) -> Scene {
+    let size = Vec2::new(800.0, 800.0);
+    let min = center - size / 2.0;
+    let stirrer = |x: f32, y: f32, velocity: Vec2| {
+        let mut motion = Motion::new(min.x + x, min.y + y);
+        motion.set_velocity(velocity, TIMESTEP / SUBSTEPS as f32);
+        Entity::new(30.0, motion)
+            .with_density(5.0)
+            .with_material(Material::RUBBER)
+    };
+
+    Scene::periodic(Periodic::from_center(center, size))
+        .with_entity(stirrer(100.0, 100.0, Vec2::new(600.0, 250.0)))
+        .with_entity(stirrer(700.0, 200.0, Vec2::new(-300.0, 550.0)))
+        .with_entity(stirrer(400.0, 700.0, Vec2::new(450.0, -400.0)))
+        .spawning_in(Vec4::new(min.x, min.y, size.x, size.y))
+}
+
+/// Square slabs held in shape by clusters: a springy one, a stiff one and one
+/// of clay that keeps the dents balls knock into it.
+fn jelly(center: Vec2) -> Scene {
+    let slab = |first: usize, x: f32, y: f32| {
+        let (side, radius) = (6, 10.0);
+        let origin = center + Vec2::new(x, y);
+        (0..side * side)
+            .map(|i| {
+                let offset = Vec2::new((i % side) as f32, (i / side) as f32) * radius * 2.0;
+                let position = origin + offset;
+                Entity::new(radius, Motion::new(position.x, position.y)).with_group(first)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    Scene::new(Circle::default())
+        .with_cluster(slab(0, -250.0, -100.0), |cluster| {
+            cluster.with_stiffness(0.2)
+        })
+        .with_cluster(slab(36, -60.0, -100.0), |cluster| cluster)
+        .with_cluster(slab(72, 130.0, -100.0), |cluster| {
+            cluster.with_stiffness(0.5).with_plasticity(2.0, 0.2)
+        })
+        .spawning_in(Vec4::new(center.x - 200.0, center.y - 380.0, 400.0, 100.0))
+}
+
+/// Two chambers joined by a narrow neck.
+fn hourglass(center: Vec2) -> Scene {
+    let (half_width, half_height, neck) = (300.0, 390.0, 40.0);
+    let vertices = [
+        (-half_width, -half_height),
+        (half_width, -half_height),
+        (half_width, -half_height + 60.0),
+        (neck, -10.0),
+        (neck, 10.0),
+        (half_width, half_height - 60.0),
+        (half_width, half_height),
+        (-half_width, half_height),
+        (-half_width, half_height - 60.0),
+        (-neck, 10.0),
+        (-neck, -10.0),
+        (-half_width, -half_height + 60.0),
+    ];
+
+    Scene::new(Polygon::new(
+        vertices
+            .iter()
+            .map(|&(x, y)| center + Vec2::new(x, y))
+            .collect(),
+    ))
+    .spawning_in(Vec4::new(
+        center.x - 240.0,
+        center.y - half_height + 20.0,
+        480.0,
+        200.0,
+    ))
+}
+
+fn entity_radius() -> RadiusDistribution {
+    RadiusDistribution::Uniform {
+        min: 15.0,
+        max: 30.0,
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        #[cfg(not(feature = "render"))]
+        None | Some("--boundary") => {
+            eprintln!(
+                "error: built without the `render` feature, only `headless` is available\n\n{}",
+                USAGE
+            );
+            process::exit(2);
+        }
+        #[cfg(feature = "render")]
+        None => Window::from_config(config(), run_window(Scene::new(Circle::default()))),
+        #[cfg(feature = "render")]
+        Some("--boundary") => match args.get(1).and_then(|name| scene(name)) {
+            Some(scene) => Window::from_config(config(), run_window(scene)),
+            None => {
+                eprintln!("error: unknown or missing boundary\n\n{}", USAGE);
+                process::exit(2);
+            }
+        },
+        Some("headless") => {
+            if let Err(err) = run_headless(&args[1..]) {
+                eprintln!("error: {}\n\n{}", err, USAGE);
+                process::exit(2);
+            }
+        }
+        Some("-h" | "--help") => println!("{}", USAGE),
+        Some(other) => {
+            eprintln!("error: unknown subcommand `{}`\n\n{}", other, USAGE);
+            process::exit(2);
         }
     }
+}
 
-    fn update_position(&mut self, dt: f32) {
-        let velocity = self.position - self.previous_position;
+#[cfg(feature = "render")]
+async fn run_window(scene: Scene) {
+    let mut app = App::new(
+        colors::BLACK,
+        scene,
+        colors::GRAY,
+        Resolver::default(),
+        entity_radius(),
+        colors::WHITE,
+        ENTITY_COUNT,
+    );
 
-        self.previous_position = self.position;
-        self.position += self.acceleration + velocity * dt * dt;
-        self.acceleration = Vec2::default();
-    }
+    app.run().await
+}
 
-    pub fn accelerate(&mut self, acceleration: Vec2) {
-        self.acceleration += acceleration;
+fn run_headless(args: &[String]) -> Result<(), String> {
+    let mut headless = Headless::default();
+    let mut count = ENTITY_COUNT;
+    let mut seed = 0;
+    let mut radius = entity_radius();
+    let mut output = None;
+    let mut resolver = Resolver::default();
+    let mut scene = Scene::new(Circle::default());
+
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for `{}`", flag))
+        };
+
+        match flag.as_str() {
+            "--steps" => headless.steps = parse(flag, value()?)?,
+            "--dt" => headless.dt = parse(flag, value()?)?,
+            "--count" => count = parse(flag, value()?)?,
+            "--seed" => seed = parse(flag, value()?)?,
+            "--radius" => radius = parse(flag, value()?)?,
+            "--output" => output = Some(value()?.clone()),
+            "--integrator" => {
+                let name = value()?;
+                resolver.integrator =
+                    integrator(name).ok_or_else(|| format!("unknown integrator `{}`", name))?;
+            }
+            "--boundary" => {
+                let name = value()?;
+                scene = self::scene(name).ok_or_else(|| format!("unknown boundary `{}`", name))?;
+            }
+            "--drag" => {
+                let drag = value()?;
+                let (linear, quadratic) = drag
+                    .split_once(',')
+                    .ok_or_else(|| format!("expected `<linear>,<quadratic>`, got `{}`", drag))?;
+                resolver.drag = Drag::new(parse(flag, linear)?, parse(flag, quadratic)?);
+            }
+            "--broadphase" => {
+                let name = value()?;
+                resolver.broadphase =
+                    broadphase(name).ok_or_else(|| format!("unknown broadphase `{}`", name))?;
+            }
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
     }
+
+    let entities = generate_entities(scene.spawn_area, radius, count, &mut Rng::new(seed));
+    let mut world = scene.into_world(resolver).with_entities(entities);
+    let report = headless.run(&mut world);
+
+    let written = match output {
+        Some(path) => File::create(&path).and_then(|file| report.write_positions(file)),
+        None => report.write_positions(io::stdout().lock()),
+    };
+    written.map_err(|err| format!("failed to write positions: {}", err))?;
+    eprint!("{}", report);
+
+    Ok(())
 }
 
-#[derive(Debug)]
-struct Constraint {
-    position: Vec2,
-    radius: f32,
-    offset: f32,
-    color: Color,
+fn parse<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{}` for `{}`", value, flag))
 }
 
-impl Constraint {
-    pub fn new(position: Vec2, radius: f32, offset: f32, color: Color) -> Self {
-        Self {
-            position,
-            radius,
-            offset,
-            color,
+/// How radii are drawn for generated entities.
+#[derive(Debug, Clone, Copy)]
+enum RadiusDistribution {
+    Fixed(f32),
+    Uniform {
+        min: f32,
+        max: f32,
+    },
+    /// Uniform in `ln(radius)`, so every order of magnitude is equally likely.
+    LogUniform {
+        min: f32,
+        max: f32,
+    },
+}
+
+impl RadiusDistribution {
+    fn sample(&self, rng: &mut Rng) -> f32 {
+        match *self {
+            Self::Fixed(radius) => radius,
+            Self::Uniform { min, max } => rng.range(min, max),
+            Self::LogUniform { min, max } => rng.range(min.ln(), max.ln()).exp(),
         }
     }
+}
 
-    pub fn draw(&self) {
-        shapes::draw_poly(
-            self.position.x,
-            self.position.y,
-            100,
-            self.radius,
-            // self.radius + self.offset * 2.5,
-            0.0,
-            self.color,
-        );
+impl FromStr for RadiusDistribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |value: &str| {
+            value
+                .parse::<f32>()
+                .ok()
+                .filter(|radius| *radius > 0.0)
+                .ok_or_else(|| format!("invalid radius `{}`", value))
+        };
+        let (log, range) = match s.strip_prefix("log:") {
+            Some(range) => (true, range),
+            None => (false, s),
+        };
+
+        match range.split_once("..") {
+            Some((min, max)) => {
+                let (min, max) = (parse(min)?, parse(max)?);
+                Ok(if log {
+                    Self::LogUniform { min, max }
+                } else {
+                    Self::Uniform { min, max }
+                })
+            }
+            None if !log => Ok(Self::Fixed(parse(range)?)),
+            None => Err(format!("expected a range after `log:` in `{}`", s)),
+        }
     }
 }
 
-impl Default for Constraint {
-    fn default() -> Self {
-        Self::new(Vec2::new(800.0, 450.0), 400.0, 25.0, colors::GRAY)
+/// A small seedable generator for placing entities, so runs with the same
+/// seed match on every machine without pulling in a windowing layer.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // Xorshift never leaves zero, so the seed is mixed first.
+        Self(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    /// Uniform in `min..max`.
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        let unit = (self.0 >> 40) as f32 / (1u64 << 24) as f32;
+        min + (max - min) * unit
     }
 }
 
-#[derive(Debug)]
-struct Resolver {
-    gravity: Vec2,
+/// Entities scattered over `spawn_area`, given as `x, y, width, height`.
+fn generate_entities(
+    spawn_area: Vec4,
+    radius: RadiusDistribution,
+    n: usize,
+    rng: &mut Rng,
+) -> Vec<Entity> {
+    (0..n)
+        .map(|_| {
+            let x = rng.range(spawn_area.x, spawn_area.x + spawn_area.z);
+            let y = rng.range(spawn_area.y, spawn_area.y + spawn_area.w);
+
+            Entity::new(radius.sample(rng), Motion::new(x, y))
+        })
+        .collect()
 }
 
-impl Resolver {
-    pub fn new(x: f32, y: f32) -> Self {
+#[cfg(feature = "render")]
+struct App {
+    background_color: Color,
+    border_color: Color,
+    entity_color: Color,
+    world: World,
+    timestep: FixedTimestep,
+}
+
+#[cfg(feature = "render")]
+impl App {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        background_color: Color,
+        scene: Scene,
+        border_color: Color,
+        resolver: Resolver,
+        entity_radius: RadiusDistribution,
+        entity_color: Color,
+        entity_count: usize,
+    ) -> Self {
+        let entities = generate_entities(
+            scene.spawn_area,
+            entity_radius,
+            entity_count,
+            &mut Rng::new(0),
+        );
+
         Self {
-            gravity: Vec2::new(x, y),
+            background_color,
+            border_color,
+            entity_color,
+            world: scene.into_world(resolver).with_entities(entities),
+            timestep: FixedTimestep::new(TIMESTEP, SUBSTEPS, MAX_STEPS_PER_FRAME),
         }
     }
 
-    fn update(&self, entities: &Vec<RefCell<Entity>>, constraint: &Constraint, dt: f32) {
-        self.apply_gravity(entities);
-        self.apply_constraint(entities, constraint);
-        self.apply_collisions(entities);
-        self.update_position(entities, dt);
-    }
+    pub async fn run(&mut self) {
+        loop {
+            if input::is_key_released(KeyCode::Escape) {
+                break;
+            };
 
-    fn update_position(&self, entities: &Vec<RefCell<Entity>>, dt: f32) {
-        entities
-            .iter()
-            .map(|entity| entity.borrow_mut())
-            .for_each(|mut entity| entity.motion.update_position(dt));
+            self.handle_input();
+            self.tick().await
+        }
     }
 
-    fn apply_gravity(&self, entities: &Vec<RefCell<Entity>>) {
-        entities
-            .iter()
-            .map(|entity| entity.borrow_mut())
-            .for_each(|mut entity| entity.motion.accelerate(self.gravity));
-    }
+    fn handle_input(&mut self) {
+        let selected = [
+            (KeyCode::Key1, "euler"),
+            (KeyCode::Key2, "semi-implicit-euler"),
+            (KeyCode::Key3, "verlet"),
+            (KeyCode::Key4, "rk4"),
+        ]
+        .into_iter()
+        .find(|(key, _)| input::is_key_pressed(*key))
+        .and_then(|(_, name)| integrator(name));
 
-    fn apply_constraint(&self, entities: &Vec<RefCell<Entity>>, constraint: &Constraint) {
-        entities
-            .iter()
-            .map(|entity| entity.borrow_mut())
-            .for_each(|mut entity| {
-                let to_entity = entity.motion.position - constraint.position;
-                let distance = to_entity.length();
-
-                if distance > constraint.radius - constraint.offset {
-                    let n = to_entity / distance;
-                    entity.motion.position =
-                        constraint.position + n * (distance - constraint.offset);
-                }
-            });
+        if let Some(integrator) = selected {
+            self.world.resolver.integrator = integrator;
+        }
     }
 
-    fn apply_collisions(&self, entities: &Vec<RefCell<Entity>>) {
-        let entity_count = entities.len();
-        let entity_offset = entities[0].borrow().radius * 2.0;
+    async fn tick(&mut self) {
+        let dt = time::get_frame_time();
 
-        for i in 0..entity_count {
-            let mut entity_a = entities[i].borrow_mut();
+        window::clear_background(self.background_color);
 
-            for k in i + 1..entity_count {
-                let mut entity_b = entities[k].borrow_mut();
-                let collision_axis = entity_a.motion.position - entity_b.motion.position;
-                let distance = collision_axis.length();
+        self.update(dt);
+        self.draw();
 
-                if distance < entity_offset {
-                    let n = collision_axis / distance;
-                    let delta = entity_offset - distance;
+        window::next_frame().await
+    }
 
-                    entity_a.motion.position += 0.5 * delta * n;
-                    entity_b.motion.position -= 0.5 * delta * n;
-                }
-            }
-        }
+    fn update(&mut self, dt: f32) {
+        self.timestep.advance(&mut self.world, dt);
     }
-}
 
-impl Default for Resolver {
-    fn default() -> Self {
-        Self::new(0.0, 10.0)
+    fn draw(&self) {
+        self.world
+            .boundaries
+            .iter()
+            .for_each(|boundary| boundary.draw(self.border_color));
+        if let Some(periodic) = &self.world.resolver.periodic {
+            periodic.draw(self.border_color);
+        }
+        self.world
+            .pressures
+            .iter()
+            .for_each(|pressure| pressure.draw(&self.world.entities, self.entity_color));
+        self.world
+            .entities
+            .iter()
+            .for_each(|entity| entity.borrow().draw(self.entity_color));
+        self.world
+            .sticks
+            .iter()
+            .for_each(|stick| stick.draw(&self.world.entities, self.border_color));
+
+        text::draw_text(
+            &format!(
+                "integrator: {} (1-4 to switch)",
+                self.world.resolver.integrator.name()
+            ),
+            20.0,
+            30.0,
+            30.0,
+            self.entity_color,
+        );
     }
 }
@@ -0,0 +1,51 @@
+/// Surface response of a body when it touches another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Material {
+    /// Fraction of the approach speed kept after a contact, from `0` (no
+    /// bounce) to `1` (perfectly elastic).
+    pub restitution: f32,
+    /// Coulomb friction coefficient bounding the tangential impulse relative
+    /// to the normal one.
+    pub friction: f32,
+}
+
+impl Material {
+    pub const RUBBER: Material = Material::new(0.85, 0.9);
+    pub const SAND: Material = Material::new(0.05, 0.8);
+    pub const ICE: Material = Material::new(0.1, 0.02);
+
+    pub const fn new(restitution: f32, friction: f32) -> Self {
+        Self {
+            restitution,
+            friction,
+        }
+    }
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self::new(0.3, 0.2)
+    }
+}
+
+/// How the coefficients of two touching materials are merged into one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombineRule {
+    Average,
+    GeometricMean,
+    Min,
+    Max,
+    Multiply,
+}
+
+impl CombineRule {
+    pub fn combine(self, a: f32, b: f32) -> f32 {
+        match self {
+            Self::Average => (a + b) * 0.5,
+            Self::GeometricMean => (a * b).sqrt(),
+            Self::Min => a.min(b),
+            Self::Max => a.max(b),
+            Self::Multiply => a * b,
+        }
+    }
+}
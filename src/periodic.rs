@@ -0,0 +1,98 @@
+use crate::{Aabb, Motion};
+use glam::Vec2;
+
+#[cfg(feature = "render")]
+use macroquad::{color::Color, shapes};
+
+/// A rectangular domain that wraps around: whatever leaves one edge comes
+/// back in at the opposite one, so there are no walls at all, as in a small
+/// piece of an endless material.
+///
+/// Contacts are found across the edges by taking the nearest image of each
+/// entity, the minimum image convention. Sticks, pressures and clusters
+/// still measure straight across the domain, so keep them clear of the
+/// edges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Periodic {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Periodic {
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        assert!(
+            min.x < max.x && min.y < max.y,
+            "a periodic domain needs a positive size"
+        );
+        Self { min, max }
+    }
+
+    pub fn from_center(center: Vec2, size: Vec2) -> Self {
+        Self::new(center - size * 0.5, center + size * 0.5)
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    /// Shortest displacement from `from` to any image of `to`.
+    pub fn minimum_image(&self, from: Vec2, to: Vec2) -> Vec2 {
+        let size = self.size();
+        let offset = to - from;
+        offset - size * (offset / size).round()
+    }
+
+    /// Shift taking `to` to its image nearest `from`, a whole number of
+    /// domain sizes each way.
+    pub fn image_offset(&self, from: Vec2, to: Vec2) -> Vec2 {
+        from + self.minimum_image(from, to) - to
+    }
+
+    /// The same point brought inside the domain.
+    pub fn wrap(&self, point: Vec2) -> Vec2 {
+        point + self.wrap_offset(point)
+    }
+
+    fn wrap_offset(&self, point: Vec2) -> Vec2 {
+        let size = self.size();
+        -size * ((point - self.min) / size).floor()
+    }
+
+    /// Brings a body back inside the domain without changing its velocity.
+    pub(crate) fn wrap_motion(&self, motion: &mut Motion) {
+        let offset = self.wrap_offset(motion.position);
+        if offset != Vec2::ZERO {
+            motion.translate(offset);
+        }
+    }
+
+    /// Shifts of the images of `bounds` that reach into the domain from
+    /// across an edge it sticks out of.
+    pub(crate) fn images(&self, bounds: &Aabb) -> impl Iterator<Item = Vec2> {
+        let size = self.size();
+        let across = |min: f32, max: f32, low: f32, high: f32, size: f32| {
+            if min < low {
+                size
+            } else if max > high {
+                -size
+            } else {
+                0.0
+            }
+        };
+        let x = across(bounds.min.x, bounds.max.x, self.min.x, self.max.x, size.x);
+        let y = across(bounds.min.y, bounds.max.y, self.min.y, self.max.y, size.y);
+
+        let corner = (x != 0.0 && y != 0.0).then(|| Vec2::new(x, y));
+        [Vec2::new(x, 0.0), Vec2::new(0.0, y)]
+            .into_iter()
+            .filter(|&offset| offset != Vec2::ZERO)
+            .chain(corner)
+    }
+
+    /// Outlines the domain.
+    #[cfg(feature = "render")]
+    pub fn draw(&self, color: Color) {
+        let size = self.size();
+        shapes::draw_rectangle_lines(self.min.x, self.min.y, size.x, size.y, 2.0, color);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{geometry::signed_area, Entity};
+use glam::Vec2;
+use std::{cell::RefCell, ops::Range};
+
+#[cfg(feature = "render")]
+use macroquad::{color::Color, math, shapes};
+
+/// Keeps the area enclosed by a ring of entities at a target, the way gas
+/// pressure holds a balloon out. Squashing one side bulges the others.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pressure {
+    /// Indices of the entities around the ring, in order.
+    pub ring: Range<usize>,
+    /// Signed area the ring is pushed towards, with the sign of
+    /// [`Pressure::area`] for the ring as laid out.
+    pub target: f32,
+    /// Resistance to changes of area, infinite for an incompressible ring.
+    /// Like [`Stick::stiffness`](crate::Stick::stiffness) it does not depend
+    /// on the step size.
+    pub stiffness: f32,
+}
+
+impl Pressure {
+    /// Holds the ring at its current area.
+    pub fn new(entities: &[RefCell<Entity>], ring: Range<usize>) -> Self {
+        assert!(
+            ring.len() >= 3,
+            "a pressure ring needs at least three entities"
+        );
+        let target = signed_area(&positions(entities, &ring));
+        Self {
+            ring,
+            target,
+            stiffness: f32::INFINITY,
+        }
+    }
+
+    /// Scales the target area, inflating the ring above `1` and letting it
+    /// sag below.
+    pub fn with_inflation(mut self, inflation: f32) -> Self {
+        self.target *= inflation;
+        self
+    }
+
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    /// Signed area currently enclosed by the ring.
+    pub fn area(&self, entities: &[RefCell<Entity>]) -> f32 {
+        signed_area(&positions(entities, &self.ring))
+    }
+
+    /// Moves every entity of the ring along the outward direction of the
+    /// area, in proportion to its inverse mass, towards the target.
+    ///
+    /// `force` accumulates over the passes of one step like it does for
+    /// sticks.
+    pub(crate) fn solve(&self, entities: &[RefCell<Entity>], force: &mut f32, dt: f32) {
+        let points = positions(entities, &self.ring);
+        let n = points.len();
+        let gradient = |i: usize| -(points[(i + 1) % n] - points[(i + n - 1) % n]).perp() / 2.0;
+
+        let inverse_mass: f32 = self
+            .ring
+            .clone()
+            .enumerate()
+            .map(|(i, index)| {
+                entities[index].borrow().inverse_mass() * gradient(i).length_squared()
+            })
+            .sum();
+        if inverse_mass == 0.0 {
+            return;
+        }
+
+        let compliance = (self.stiffness * dt * dt).recip();
+        let error = signed_area(&points) - self.target;
+        let change = -(error + compliance * *force) / (inverse_mass + compliance);
+        *force += change;
+
+        for (i, index) in self.ring.clone().enumerate() {
+            let mut entity = entities[index].borrow_mut();
+            let position = entity.motion.position;
+            entity.displace(position, gradient(i) * change);
+        }
+    }
+
+    /// Fills the outline of the ring.
+    #[cfg(feature = "render")]
+    pub fn draw(&self, entities: &[RefCell<Entity>], color: Color) {
+        let points = positions(entities, &self.ring);
+        let center = points.iter().sum::<Vec2>() / points.len() as f32;
+        let point = |v: Vec2| math::vec2(v.x, v.y);
+        for (i, &corner) in points.iter().enumerate() {
+            let next = points[(i + 1) % points.len()];
+            shapes::draw_triangle(point(center), point(corner), point(next), color);
+        }
+    }
+}
+
+fn positions(entities: &[RefCell<Entity>], ring: &Range<usize>) -> Vec<Vec2> {
+    ring.clone()
+        .map(|index| entities[index].borrow().motion.position)
+        .collect()
+}
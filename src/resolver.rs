@@ -0,0 +1,583 @@
+use crate::{
+    collision::{collide, ContactPoint},
+    Aabb, Boundary, Broadphase, Cluster, CombineRule, Contact, Drag, Entity, Integrator, Material,
+    Periodic, Pressure, Stick, UniformGrid, Verlet,
+};
+use glam::Vec2;
+use std::{cell::RefCell, mem};
+
+/// Cosine above which boundary contacts count as pressing on the same wall.
+const SAME_NORMAL: f32 = 0.99;
+
+/// Advances a set of entities by one step: gravity, sticks (dropping those
+/// stretched past tearing), pressures and clusters, pairwise collisions,
+/// boundary contacts, friction at all of them and finally integration, which
+/// moves kinematic entities along their paths instead, and moving boundaries
+/// on.
+#[derive(Debug)]
+pub struct Resolver {
+    /// Acceleration applied to every entity, in units per second squared.
+    pub gravity: Vec2,
+    /// Drag for every entity that does not carry its own.
+    pub drag: Drag,
+    pub integrator: Box<dyn Integrator>,
+    pub broadphase: Box<dyn Broadphase>,
+    pub restitution_rule: CombineRule,
+    pub friction_rule: CombineRule,
+    /// Approach speed below which contacts do not bounce, which keeps resting
+    /// bodies from jittering under gravity.
+    pub restitution_threshold: f32,
+    /// Passes over the sticks, pressures and clusters each step. More passes
+    /// let long chains of sticks settle instead of stretching.
+    pub constraint_iterations: usize,
+    /// Wraps entities around a rectangular domain instead of leaving them to
+    /// boundaries when set.
+    pub periodic: Option<Periodic>,
+    pairs: Vec<(usize, usize)>,
+    ghosts: Vec<(Aabb, usize)>,
+    swept: Vec<(Aabb, usize)>,
+    hull: Vec<Vec2>,
+    contacts: Vec<Contact>,
+    sliding: Vec<Sliding>,
+    constraint_forces: Vec<f32>,
+}
+
+/// Contact left to friction once every normal has been resolved, so the
+/// spin one contact passes through a body to another does not read as
+/// sliding at either.
+#[derive(Debug, Clone, Copy)]
+struct Sliding {
+    a: usize,
+    b: Option<usize>,
+    normal: Vec2,
+    point: Vec2,
+    /// Velocity of the boundary at `point` when `b` is `None`.
+    surface: Vec2,
+    /// Shift taking `b` to the image `a` touched across a periodic wrap.
+    shift: Vec2,
+    /// Largest tangential velocity change friction may make.
+    limit: f32,
+}
+
+impl Resolver {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self {
+            gravity: Vec2::new(x, y),
+            drag: Drag::NONE,
+            integrator: Box::new(Verlet),
+            broadphase: Box::new(UniformGrid::new()),
+            restitution_rule: CombineRule::Max,
+            friction_rule: CombineRule::GeometricMean,
+            restitution_threshold: 20.0,
+            constraint_iterations: 8,
+            periodic: None,
+            pairs: Vec::new(),
+            ghosts: Vec::new(),
+            swept: Vec::new(),
+            hull: Vec::new(),
+            contacts: Vec::new(),
+            sliding: Vec::new(),
+            constraint_forces: Vec::new(),
+        }
+    }
+
+    pub fn with_drag(mut self, drag: Drag) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    pub fn with_integrator(mut self, integrator: impl Integrator + 'static) -> Self {
+        self.integrator = Box::new(integrator);
+        self
+    }
+
+    pub fn with_broadphase(mut self, broadphase: impl Broadphase + 'static) -> Self {
+        self.broadphase = Box::new(broadphase);
+        self
+    }
+
+    pub fn with_periodic(mut self, periodic: Periodic) -> Self {
+        self.periodic = Some(periodic);
+        self
+    }
+
+    pub fn update(
+        &mut self,
+        entities: &[RefCell<Entity>],
+        boundaries: &mut [Box<dyn Boundary>],
+        sticks: &mut Vec<Stick>,
+        pressures: &[Pressure],
+        clusters: &mut [Cluster],
+        dt: f32,
+    ) {
+        self.apply_gravity(entities);
+        self.apply_constraints(entities, sticks, pressures, clusters, dt);
+        self.sliding.clear();
+        self.apply_collisions(entities, dt);
+        self.apply_boundaries(entities, boundaries, dt);
+        self.apply_friction(entities, dt);
+        self.update_position(entities, dt);
+        for boundary in boundaries {
+            boundary.advance(dt);
+        }
+    }
+
+    fn update_position(&self, entities: &[RefCell<Entity>], dt: f32) {
+        for entity in entities {
+            let entity = &mut *entity.borrow_mut();
+            if let Some(kinematic) = &mut entity.kinematic {
+                kinematic.advance(&mut entity.motion, dt);
+            } else if !entity.is_static() {
+                entity.motion.rotate(dt);
+                let drag = entity.drag.unwrap_or(self.drag);
+                self.integrator.integrate(
+                    &mut entity.motion,
+                    &|_, velocity| drag.acceleration(velocity),
+                    dt,
+                );
+                if let Some(periodic) = &self.periodic {
+                    periodic.wrap_motion(&mut entity.motion);
+                }
+            }
+        }
+    }
+
+    fn apply_gravity(&self, entities: &[RefCell<Entity>]) {
+        entities
+            .iter()
+            .map(|entity| entity.borrow_mut())
+            .filter(|entity| !entity.is_static())
+            .for_each(|mut entity| entity.motion.accelerate(self.gravity));
+    }
+
+    fn apply_constraints(
+        &mut self,
+        entities: &[RefCell<Entity>],
+        sticks: &mut Vec<Stick>,
+        pressures: &[Pressure],
+        clusters: &mut [Cluster],
+        dt: f32,
+    ) {
+        sticks.retain(|stick| !stick.is_torn(entities));
+        // Plastic clusters yield to the last step's knocks before springing
+        // back from what is left.
+        for cluster in clusters.iter_mut() {
+            cluster.deform(entities);
+        }
+        self.constraint_forces.clear();
+        self.constraint_forces
+            .resize(sticks.len() + pressures.len(), 0.0);
+
+        for _ in 0..self.constraint_iterations {
+            let (stick_forces, pressure_forces) = self.constraint_forces.split_at_mut(sticks.len());
+            for (stick, force) in sticks.iter().zip(stick_forces) {
+                stick.solve(entities, force, dt);
+            }
+            for (pressure, force) in pressures.iter().zip(pressure_forces) {
+                pressure.solve(entities, force, dt);
+            }
+            for cluster in clusters.iter() {
+                cluster.solve(entities, self.constraint_iterations);
+            }
+        }
+    }
+
+    fn apply_boundaries(
+        &mut self,
+        entities: &[RefCell<Entity>],
+        boundaries: &[Box<dyn Boundary>],
+        dt: f32,
+    ) {
+        let (mut hull, mut contacts) = (mem::take(&mut self.hull), mem::take(&mut self.contacts));
+
+        for (index, mut entity) in entities
+            .iter()
+            .map(|entity| entity.borrow_mut())
+            .enumerate()
+            .filter(|(_, entity)| !entity.is_static())
+        {
+            for boundary in boundaries {
+                entity.hull(&mut hull);
+                contacts.clear();
+                boundary.contacts(&hull, entity.radius, &mut contacts);
+
+                // Corners pressed against the same wall are solved together,
+                // like the points of an entity manifold.
+                while let Some(first) = contacts.pop() {
+                    let mut points = [ContactPoint {
+                        position: first.point,
+                        depth: first.depth,
+                    }; 2];
+                    let mut count = 1;
+                    for contact in contacts.iter() {
+                        if contact.normal.dot(first.normal) > SAME_NORMAL {
+                            // Keep the deepest corner and the one furthest
+                            // from it, which span the face.
+                            let point = ContactPoint {
+                                position: contact.point,
+                                depth: contact.depth,
+                            };
+                            if count == 1 {
+                                count = 2;
+                                points[1] = point;
+                            } else if point.position.distance_squared(points[0].position)
+                                > points[1].position.distance_squared(points[0].position)
+                            {
+                                points[1] = point;
+                            }
+                        }
+                    }
+                    contacts.retain(|contact| contact.normal.dot(first.normal) <= SAME_NORMAL);
+
+                    let sliding = self.resolve_manifold(
+                        &mut entity,
+                        Against::Boundary(&**boundary),
+                        first.normal,
+                        &points[..count],
+                        dt,
+                    );
+                    self.sliding.extend(sliding.map(|(point, limit)| Sliding {
+                        a: index,
+                        b: None,
+                        normal: first.normal,
+                        point,
+                        surface: boundary.velocity_at(point),
+                        shift: Vec2::ZERO,
+                        limit,
+                    }));
+                }
+            }
+        }
+
+        self.hull = hull;
+        self.contacts = contacts;
+    }
+
+    fn apply_collisions(&mut self, entities: &[RefCell<Entity>], dt: f32) {
+        self.broadphase.pairs(entities, &mut self.pairs);
+        if let Some(periodic) = self.periodic {
+            self.add_wrapped_pairs(entities, &periodic);
+        }
+        let (mut hull_a, mut hull_b) = (mem::take(&mut self.hull), Vec::new());
+
+        for &(a, b) in &self.pairs {
+            let mut entity_a = entities[a].borrow_mut();
+            let mut entity_b = entities[b].borrow_mut();
+            if !entity_a.collides_with(&entity_b) {
+                continue;
+            }
+
+            // Across a wrap `b` is moved to the image nearest `a` for the
+            // length of the contact.
+            let shift = self.periodic.map_or(Vec2::ZERO, |periodic| {
+                periodic.image_offset(entity_a.motion.position, entity_b.motion.position)
+            });
+            entity_b.motion.translate(shift);
+
+            entity_a.hull(&mut hull_a);
+            entity_b.hull(&mut hull_b);
+            let sliding =
+                collide(&hull_a, entity_a.radius, &hull_b, entity_b.radius).and_then(|manifold| {
+                    let sliding = self.resolve_manifold(
+                        &mut entity_a,
+                        Against::Entity(&mut entity_b),
+                        manifold.normal,
+                        manifold.points(),
+                        dt,
+                    );
+                    sliding.map(|(point, limit)| Sliding {
+                        a,
+                        b: Some(b),
+                        normal: manifold.normal,
+                        point,
+                        surface: Vec2::ZERO,
+                        shift,
+                        limit,
+                    })
+                });
+            self.sliding.extend(sliding);
+            entity_b.motion.translate(-shift);
+        }
+
+        self.hull = hull_a;
+    }
+
+    /// Adds the pairs that only touch across the edges of a periodic domain.
+    ///
+    /// The images of the entities sticking out of the domain are gathered
+    /// once and swept against every entity along x, rather than each being
+    /// looked up in the broadphase.
+    fn add_wrapped_pairs(&mut self, entities: &[RefCell<Entity>], periodic: &Periodic) {
+        self.ghosts.clear();
+        for (a, entity) in entities.iter().enumerate() {
+            let bounds = entity.borrow().bounds();
+            self.ghosts.extend(
+                periodic
+                    .images(&bounds)
+                    .map(|offset| (Aabb::new(bounds.min + offset, bounds.max + offset), a)),
+            );
+        }
+        if self.ghosts.is_empty() {
+            return;
+        }
+
+        self.swept.clear();
+        self.swept.extend(
+            entities
+                .iter()
+                .enumerate()
+                .map(|(b, entity)| (entity.borrow().bounds(), b)),
+        );
+        let by_min_x = |a: &(Aabb, usize), b: &(Aabb, usize)| a.0.min.x.total_cmp(&b.0.min.x);
+        self.ghosts.sort_unstable_by(by_min_x);
+        self.swept.sort_unstable_by(by_min_x);
+
+        // Each overlap is found from whichever of the two starts further
+        // left, scanning the other list until it starts past its right edge.
+        let (ghosts, swept) = (&self.ghosts, &self.swept);
+        let (mut g, mut e) = (0, 0);
+        while g < ghosts.len() && e < swept.len() {
+            let (first, others) = if ghosts[g].0.min.x <= swept[e].0.min.x {
+                g += 1;
+                (ghosts[g - 1], &swept[e..])
+            } else {
+                e += 1;
+                (swept[e - 1], &ghosts[g..])
+            };
+            let (bounds, a) = first;
+            self.pairs.extend(
+                others
+                    .iter()
+                    .take_while(|(other, _)| other.min.x <= bounds.max.x)
+                    .filter(|&&(other, b)| b != a && other.overlaps(&bounds))
+                    .map(|&(_, b)| (a.min(b), a.max(b))),
+            );
+        }
+
+        // Both entities of a pair may stick out, and a pair may touch
+        // directly as well as across the wrap.
+        self.pairs.sort_unstable();
+        self.pairs.dedup();
+    }
+
+    /// Pushes `a` out of `b`, an entity or a boundary, along `normal` at one
+    /// or two `points`, then corrects the relative velocity there for
+    /// restitution.
+    ///
+    /// The projection injects velocity through the Verlet state, which on its
+    /// own leaves the contact roughly inelastic. The normal impulse then
+    /// restores a `restitution` fraction of the approach speed. Two points are
+    /// solved together through their coupled effective mass, so a box landing
+    /// flat on its face is not spun by one corner being handled before the
+    /// other.
+    ///
+    /// Returns where friction should act and the most it may change the
+    /// sliding speed there: `friction` times the normal change.
+    fn resolve_manifold(
+        &self,
+        a: &mut Entity,
+        mut b: Against,
+        normal: Vec2,
+        points: &[ContactPoint],
+        dt: f32,
+    ) -> Option<(Vec2, f32)> {
+        let relative_velocity = |a: &Entity, b: &Against, point: Vec2| {
+            a.velocity_at(point, dt) - b.velocity_at(point, dt)
+        };
+
+        let positions = [points[0].position, points[points.len() - 1].position];
+        let coupling = effective_inverse_mass(a, b.entity(), normal, positions);
+        if coupling[0][0] == 0.0 {
+            return None;
+        }
+
+        // Each side moves in proportion to its inverse mass at the contact, so
+        // a static entity takes none of the correction.
+        let before = positions.map(|point| relative_velocity(a, &b, point));
+        let depths = [points[0].depth, points[points.len() - 1].depth];
+        let corrections = solve(&coupling, depths, points.len());
+        for (point, correction) in positions.iter().zip(corrections).take(points.len()) {
+            a.displace(*point, normal * correction);
+            if let Against::Entity(b) = &mut b {
+                b.displace(*point, -normal * correction);
+            }
+        }
+
+        let material_b = b.material();
+        let restitution = self
+            .restitution_rule
+            .combine(a.material.restitution, material_b.restitution);
+        let friction = self
+            .friction_rule
+            .combine(a.material.friction, material_b.friction);
+
+        // Only approaching points take part, so a box landing on one corner
+        // is free to rotate about it.
+        let (mut active, mut count) = ([0; 2], 0);
+        for (i, velocity) in before.iter().enumerate().take(points.len()) {
+            if velocity.dot(normal) < 0.0 {
+                active[count] = i;
+                count += 1;
+            }
+        }
+        if count == 0 {
+            return None;
+        }
+
+        let mut changes = [0.0; 2];
+        for k in 0..count {
+            let (approach, point) = (before[active[k]].dot(normal), positions[active[k]]);
+            let separation = relative_velocity(a, &b, point).dot(normal);
+            let target = if -approach > self.restitution_threshold {
+                -restitution * approach
+            } else {
+                separation
+            };
+            changes[k] = target - separation;
+        }
+
+        let coupling = effective_inverse_mass(
+            a,
+            b.entity(),
+            normal,
+            [positions[active[0]], positions[active[count - 1]]],
+        );
+        let impulses = solve(&coupling, changes, count);
+        for k in 0..count {
+            let (point, impulse) = (positions[active[k]], normal * impulses[k]);
+            a.apply_impulse(point, impulse, dt);
+            if let Against::Entity(b) = &mut b {
+                b.apply_impulse(point, -impulse, dt);
+            }
+        }
+
+        // Friction acts once at the middle of the touching points, as working
+        // through them in turn would trade sliding at one for spin at the
+        // other. It is bounded by the normal change the contact made there.
+        let (mut point, mut normal_change) = (Vec2::ZERO, 0.0);
+        for (position, before) in positions.iter().zip(before).take(points.len()) {
+            let after = relative_velocity(a, &b, *position);
+            point += *position / points.len() as f32;
+            normal_change += (after - before).dot(normal) / points.len() as f32;
+        }
+        Some((point, friction * normal_change))
+    }
+
+    /// Opposes sliding at every contact recorded this step, up to its limit.
+    fn apply_friction(&self, entities: &[RefCell<Entity>], dt: f32) {
+        for sliding in &self.sliding {
+            let mut a = entities[sliding.a].borrow_mut();
+            let mut b = sliding.b.map(|b| entities[b].borrow_mut());
+            // The same point on `b` itself rather than the image `a` touched.
+            let (point, point_b) = (sliding.point, sliding.point - sliding.shift);
+
+            let velocity = a.velocity_at(point, dt)
+                - b.as_ref()
+                    .map_or(sliding.surface, |b| b.velocity_at(point_b, dt));
+            let tangential = velocity - velocity.dot(sliding.normal) * sliding.normal;
+            let speed = tangential.length();
+            if speed <= f32::EPSILON {
+                continue;
+            }
+
+            let tangent = tangential / speed;
+            let inverse_mass = a.inverse_mass_at(point, tangent)
+                + b.as_ref()
+                    .map_or(0.0, |b| b.inverse_mass_at(point_b, tangent));
+            let impulse = -tangent * speed.min(sliding.limit) / inverse_mass;
+            a.apply_impulse(point, impulse, dt);
+            if let Some(b) = b.as_mut() {
+                b.apply_impulse(point_b, -impulse, dt);
+            }
+        }
+    }
+}
+
+/// What an entity is pressed against in a contact.
+enum Against<'a> {
+    Entity(&'a mut Entity),
+    Boundary(&'a dyn Boundary),
+}
+
+impl Against<'_> {
+    fn entity(&self) -> Option<&Entity> {
+        match self {
+            Against::Entity(entity) => Some(entity),
+            Against::Boundary(_) => None,
+        }
+    }
+
+    fn material(&self) -> Material {
+        match self {
+            Against::Entity(entity) => entity.material,
+            Against::Boundary(boundary) => *boundary.material(),
+        }
+    }
+
+    fn velocity_at(&self, point: Vec2, dt: f32) -> Vec2 {
+        match self {
+            Against::Entity(entity) => entity.velocity_at(point, dt),
+            Against::Boundary(boundary) => boundary.velocity_at(point),
+        }
+    }
+}
+
+/// Inverse mass coupling two contact points along `normal`: entry `[i][j]` is
+/// the normal velocity change at point `i` per unit impulse at point `j`.
+fn effective_inverse_mass(
+    a: &Entity,
+    b: Option<&Entity>,
+    normal: Vec2,
+    points: [Vec2; 2],
+) -> [[f32; 2]; 2] {
+    let entry = |body: &Entity, i: usize, j: usize| {
+        let lever = |point: Vec2| (point - body.motion.position).perp_dot(normal);
+        body.inverse_mass() + lever(points[i]) * lever(points[j]) * body.inverse_inertia()
+    };
+
+    let mut coupling = [[0.0; 2]; 2];
+    for (i, row) in coupling.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = entry(a, i, j) + b.map_or(0.0, |b| entry(b, i, j));
+        }
+    }
+    coupling
+}
+
+/// Impulses at the first `count` points that change their normal velocities
+/// (or depths) by `changes`, never pulling one point to push the other.
+fn solve(coupling: &[[f32; 2]; 2], changes: [f32; 2], count: usize) -> [f32; 2] {
+    let [[k00, k01], [_, k11]] = *coupling;
+    let alone = |i: usize| changes[i] / coupling[i][i];
+
+    if count == 1 {
+        return [alone(0), 0.0];
+    }
+
+    let determinant = k00 * k11 - k01 * k01;
+    if determinant <= 1e-6 * k00 * k11 {
+        // Both points sit on the same line through the centres of mass and
+        // share the work.
+        return [alone(0) / 2.0, alone(1) / 2.0];
+    }
+
+    let impulses = [
+        (k11 * changes[0] - k01 * changes[1]) / determinant,
+        (k00 * changes[1] - k01 * changes[0]) / determinant,
+    ];
+    let same_sign = |impulse: f32, change: f32| impulse * change >= 0.0;
+    match (
+        same_sign(impulses[0], changes[0]),
+        same_sign(impulses[1], changes[1]),
+    ) {
+        (true, true) => impulses,
+        (false, _) => [0.0, alone(1)],
+        (true, false) => [alone(0), 0.0],
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new(0.0, 1000.0)
+    }
+}
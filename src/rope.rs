@@ -0,0 +1,120 @@
+use crate::{Entity, Motion, Stick, World};
+use glam::Vec2;
+use std::ops::Range;
+
+/// Builds a rope or chain of round links joined by sticks, laid out in a
+/// straight line from `start` to `end`.
+///
+/// The links are plain Verlet particles: the rope bends, swings and drapes
+/// over things only because its sticks pull neighbouring links back together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rope {
+    pub start: Vec2,
+    pub end: Vec2,
+    /// Number of entities, the first at `start` and the last at `end`.
+    pub links: usize,
+    pub radius: f32,
+    /// Total length of the sticks, which makes the rope sag between its ends
+    /// when longer than the distance between them.
+    pub length: f32,
+    /// Stiffness of every stick, see [`Stick::stiffness`].
+    pub stiffness: f32,
+    /// Makes the first link static, hanging the rope from it.
+    pub pin_start: bool,
+    /// Makes the last link static.
+    pub pin_end: bool,
+    /// Lets links collide with each other. Off by default, as neighbours
+    /// closer than twice the radius would push each other apart.
+    pub self_collision: bool,
+}
+
+impl Rope {
+    pub fn new(start: Vec2, end: Vec2, links: usize) -> Self {
+        assert!(links >= 2, "a rope needs at least two links");
+        Self {
+            start,
+            end,
+            links,
+            radius: 5.0,
+            length: start.distance(end),
+            stiffness: f32::INFINITY,
+            pin_start: false,
+            pin_end: false,
+            self_collision: false,
+        }
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_length(mut self, length: f32) -> Self {
+        self.length = length;
+        self
+    }
+
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    pub fn pin_start(mut self) -> Self {
+        self.pin_start = true;
+        self
+    }
+
+    pub fn pin_end(mut self) -> Self {
+        self.pin_end = true;
+        self
+    }
+
+    pub fn self_colliding(mut self) -> Self {
+        self.self_collision = true;
+        self
+    }
+
+    /// The links and the sticks between them, numbered as if the first link
+    /// will be stored at index `first`.
+    ///
+    /// Without self-collision the links share `first` as their
+    /// [`Entity::group`].
+    pub fn build(&self, first: usize) -> (Vec<Entity>, Vec<Stick>) {
+        let last = self.links - 1;
+        let entities = (0..self.links)
+            .map(|i| {
+                let position = self.start.lerp(self.end, i as f32 / last as f32);
+                let entity = Entity::new(self.radius, Motion::new(position.x, position.y));
+                let entity = if (i == 0 && self.pin_start) || (i == last && self.pin_end) {
+                    entity.pinned()
+                } else {
+                    entity
+                };
+                if self.self_collision {
+                    entity
+                } else {
+                    entity.with_group(first)
+                }
+            })
+            .collect();
+
+        let spacing = self.length / last as f32;
+        let sticks = (first..first + last)
+            .map(|i| Stick::new(i, i + 1, spacing).with_stiffness(self.stiffness))
+            .collect();
+
+        (entities, sticks)
+    }
+
+    /// Adds the rope to `world`, returning the indices of its links from
+    /// `start` to `end`.
+    pub fn add_to(&self, world: &mut World) -> Range<usize> {
+        let first = world.entities.len();
+        let (entities, sticks) = self.build(first);
+        entities
+            .into_iter()
+            .for_each(|entity| world.add_entity(entity));
+        world.sticks.extend(sticks);
+        first..world.entities.len()
+    }
+}
@@ -0,0 +1,131 @@
+use crate::Entity;
+use glam::Vec2;
+use std::cell::RefCell;
+
+#[cfg(feature = "render")]
+use macroquad::{color::Color, shapes};
+
+/// Keeps two entities a set distance apart, measured between anchor points
+/// fixed to each of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stick {
+    /// Indices of the linked entities in the world.
+    pub a: usize,
+    pub b: usize,
+    /// Attachment points relative to each entity's position at zero angle.
+    pub anchor_a: Vec2,
+    pub anchor_b: Vec2,
+    /// Distance the stick holds the anchors at.
+    pub length: f32,
+    /// Force per unit of stretch, infinite for a rigid stick. A finite value
+    /// gives an elastic link that springs back, independent of the step size.
+    pub stiffness: f32,
+    /// Ratio of the current to the rest length past which the stick breaks
+    /// and is removed from the world.
+    pub tear_ratio: Option<f32>,
+    /// Whether [`Stick::draw`] shows the stick.
+    pub visible: bool,
+}
+
+impl Stick {
+    /// A rigid stick between the positions of `a` and `b`.
+    pub fn new(a: usize, b: usize, length: f32) -> Self {
+        assert_ne!(a, b, "a stick needs two different entities");
+        Self {
+            a,
+            b,
+            anchor_a: Vec2::ZERO,
+            anchor_b: Vec2::ZERO,
+            length,
+            stiffness: f32::INFINITY,
+            tear_ratio: None,
+            visible: true,
+        }
+    }
+
+    /// A rigid stick holding `a` and `b` at their current distance.
+    pub fn between(entities: &[RefCell<Entity>], a: usize, b: usize) -> Self {
+        let length = entities[a]
+            .borrow()
+            .motion
+            .position
+            .distance(entities[b].borrow().motion.position);
+        Self::new(a, b, length)
+    }
+
+    pub fn with_anchors(mut self, anchor_a: Vec2, anchor_b: Vec2) -> Self {
+        self.anchor_a = anchor_a;
+        self.anchor_b = anchor_b;
+        self
+    }
+
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    pub fn with_tearing(mut self, tear_ratio: f32) -> Self {
+        self.tear_ratio = Some(tear_ratio);
+        self
+    }
+
+    /// Keeps the stick working but leaves it out of drawing, such as the
+    /// bracing inside a cloth.
+    pub fn hidden(mut self) -> Self {
+        self.visible = false;
+        self
+    }
+
+    /// Whether the stick is stretched past its tear ratio.
+    pub fn is_torn(&self, entities: &[RefCell<Entity>]) -> bool {
+        let (start, end) = self.ends(entities);
+        self.tear_ratio
+            .is_some_and(|ratio| start.distance(end) > self.length * ratio)
+    }
+
+    /// Current anchor positions in world space.
+    pub fn ends(&self, entities: &[RefCell<Entity>]) -> (Vec2, Vec2) {
+        (
+            entities[self.a].borrow().world_point(self.anchor_a),
+            entities[self.b].borrow().world_point(self.anchor_b),
+        )
+    }
+
+    /// Moves both ends towards the rest length, each in proportion to its
+    /// inverse mass along the stick, so a static end stays put.
+    ///
+    /// `force` accumulates the stick's pull over the passes of one step (as a
+    /// force times `dt` squared), which a finite stiffness uses to settle on a
+    /// spring's stretch rather than removing a fixed share of it every pass.
+    pub(crate) fn solve(&self, entities: &[RefCell<Entity>], force: &mut f32, dt: f32) {
+        let (mut a, mut b) = (entities[self.a].borrow_mut(), entities[self.b].borrow_mut());
+        let (start, end) = (a.world_point(self.anchor_a), b.world_point(self.anchor_b));
+
+        let offset = end - start;
+        let direction = match offset.try_normalize() {
+            Some(direction) => direction,
+            None => return,
+        };
+        let inverse_mass = a.inverse_mass_at(start, direction) + b.inverse_mass_at(end, direction);
+        if inverse_mass == 0.0 {
+            return;
+        }
+
+        let compliance = (self.stiffness * dt * dt).recip();
+        let stretch = offset.length() - self.length;
+        let change = -(stretch + compliance * *force) / (inverse_mass + compliance);
+        *force += change;
+
+        a.displace(start, -direction * change);
+        b.displace(end, direction * change);
+    }
+
+    #[cfg(feature = "render")]
+    pub fn draw(&self, entities: &[RefCell<Entity>], color: Color) {
+        if !self.visible {
+            return;
+        }
+        let (start, end) = self.ends(entities);
+        shapes::draw_line(start.x, start.y, end.x, end.y, 2.0, color);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::World;
+
+/// Decouples the simulation rate from the display rate.
+///
+/// Frame time is accumulated and consumed in fixed `step` sized chunks, each
+/// of which is split into `substeps` world updates. At most `max_steps` steps
+/// are taken per frame; any remaining whole steps of backlog are dropped so a
+/// long hitch can not snowball into ever longer frames.
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    pub step: f32,
+    pub substeps: usize,
+    pub max_steps: usize,
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    pub fn new(step: f32, substeps: usize, max_steps: usize) -> Self {
+        assert!(step > 0.0, "a fixed timestep needs a positive step");
+        Self {
+            step,
+            substeps: substeps.max(1),
+            max_steps,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Consumes `frame_time` seconds, returning the number of fixed steps taken.
+    pub fn advance(&mut self, world: &mut World, frame_time: f32) -> usize {
+        let dt = self.step / self.substeps as f32;
+        let mut steps = 0;
+
+        self.accumulator += frame_time;
+        while self.accumulator >= self.step && steps < self.max_steps {
+            (0..self.substeps).for_each(|_| world.update(dt));
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        // Keep only the part of the backlog short of a whole step, so the
+        // next frame starts fresh and `alpha` stays below one.
+        if steps == self.max_steps {
+            self.accumulator %= self.step;
+        }
+
+        steps
+    }
+
+    /// How far the accumulator is into the next step, in the range `0..1`
+    /// after [`FixedTimestep::advance`].
+    pub fn alpha(&self) -> f32 {
+        self.accumulator / self.step
+    }
+
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self::new(1.0 / 60.0, 1, 5)
+    }
+}
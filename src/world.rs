@@ -0,0 +1,111 @@
+use crate::{Aabb, Boundary, Circle, Cluster, Entity, Periodic, Pressure, Resolver, Stick};
+use std::cell::RefCell;
+
+/// Everything needed to step a simulation: the bodies, the static colliders
+/// around them, the sticks, pressures and clusters linking bodies and the
+/// resolver that moves them.
+#[derive(Debug)]
+pub struct World {
+    pub entities: Vec<RefCell<Entity>>,
+    pub boundaries: Vec<Box<dyn Boundary>>,
+    pub sticks: Vec<Stick>,
+    pub pressures: Vec<Pressure>,
+    pub clusters: Vec<Cluster>,
+    pub resolver: Resolver,
+}
+
+impl World {
+    pub fn new(boundary: impl Boundary + 'static, resolver: Resolver) -> Self {
+        Self {
+            entities: Vec::new(),
+            boundaries: vec![Box::new(boundary)],
+            sticks: Vec::new(),
+            pressures: Vec::new(),
+            clusters: Vec::new(),
+            resolver,
+        }
+    }
+
+    /// A world without walls that wraps around `periodic`, see
+    /// [`Resolver::periodic`].
+    pub fn periodic(periodic: Periodic, resolver: Resolver) -> Self {
+        Self {
+            entities: Vec::new(),
+            boundaries: Vec::new(),
+            sticks: Vec::new(),
+            pressures: Vec::new(),
+            clusters: Vec::new(),
+            resolver: resolver.with_periodic(periodic),
+        }
+    }
+
+    pub fn with_boundary(mut self, boundary: impl Boundary + 'static) -> Self {
+        self.add_boundary(boundary);
+        self
+    }
+
+    pub fn with_boundaries(
+        mut self,
+        boundaries: impl IntoIterator<Item = Box<dyn Boundary>>,
+    ) -> Self {
+        self.boundaries.extend(boundaries);
+        self
+    }
+
+    pub fn add_boundary(&mut self, boundary: impl Boundary + 'static) {
+        self.boundaries.push(Box::new(boundary));
+    }
+
+    pub fn with_entities(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+        self.entities.extend(entities.into_iter().map(RefCell::new));
+        self
+    }
+
+    pub fn add_entity(&mut self, entity: Entity) {
+        self.entities.push(RefCell::new(entity));
+    }
+
+    pub fn with_sticks(mut self, sticks: impl IntoIterator<Item = Stick>) -> Self {
+        self.sticks.extend(sticks);
+        self
+    }
+
+    pub fn add_stick(&mut self, stick: Stick) {
+        self.sticks.push(stick);
+    }
+
+    pub fn add_pressure(&mut self, pressure: Pressure) {
+        self.pressures.push(pressure);
+    }
+
+    pub fn add_cluster(&mut self, cluster: Cluster) {
+        self.clusters.push(cluster);
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.resolver.update(
+            &self.entities,
+            &mut self.boundaries,
+            &mut self.sticks,
+            &self.pressures,
+            &mut self.clusters,
+            dt,
+        );
+    }
+
+    /// Indices of the entities whose bounds overlap `region`, answered by the
+    /// resolver's broadphase.
+    pub fn query(&mut self, region: &Aabb) -> Vec<usize> {
+        let mut results = Vec::new();
+        self.resolver
+            .broadphase
+            .query(&self.entities, region, &mut results);
+        results
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new(Circle::default(), Resolver::default())
+    }
+}
@@ -0,0 +1,64 @@
+use simple_physics_engine::{Blob, Circle, Entity, Motion, Resolver, Vec2, World};
+
+/// Area of a blob dropped on the floor of a circle after two seconds, as a
+/// fraction of the area it started with, and the world it ended up in.
+fn settle(blob: Blob, load: usize) -> (f32, World) {
+    let mut world = World::new(Circle::new(Vec2::ZERO, 300.0), Resolver::default());
+    blob.add_to(&mut world);
+    for i in 0..load {
+        let x = -60.0 + 24.0 * i as f32;
+        world.add_entity(Entity::new(10.0, Motion::new(x, 100.0)));
+    }
+    let start = world.pressures[0].area(&world.entities);
+
+    for _ in 0..480 {
+        world.update(1.0 / 240.0);
+    }
+
+    let ratio = world.pressures[0].area(&world.entities) / start;
+    (ratio, world)
+}
+
+#[test]
+fn pressure_holds_a_blob_up_under_load() {
+    let blob = Blob::new(Vec2::new(0.0, 200.0), 60.0, 24);
+    let (ratio, world) = settle(blob, 6);
+
+    assert!(ratio > 0.98, "kept {} of its area", ratio);
+    let center = (0..24).fold(Vec2::ZERO, |sum, i| {
+        sum + world.entities[i].borrow().motion.position / 24.0
+    });
+    for ball in 24..30 {
+        let distance = world.entities[ball]
+            .borrow()
+            .motion
+            .position
+            .distance(center);
+        assert!(
+            distance > 50.0,
+            "ball {} sank to {} from the centre",
+            ball,
+            distance
+        );
+    }
+}
+
+#[test]
+fn soft_pressure_lets_blobs_squash() {
+    let blob = Blob::new(Vec2::new(0.0, 200.0), 60.0, 24);
+    let (firm, _) = settle(blob, 6);
+    let (soft, _) = settle(blob.with_pressure_stiffness(20.0), 6);
+
+    assert!(soft < firm - 0.05, "soft kept {} against {}", soft, firm);
+    assert!(soft > 0.5, "soft collapsed to {}", soft);
+}
+
+#[test]
+fn inflation_sets_the_area_blobs_settle_at() {
+    let blob = Blob::new(Vec2::new(0.0, 200.0), 60.0, 24).with_rim_stiffness(1e4);
+    let (deflated, _) = settle(blob.with_inflation(0.7), 0);
+    let (inflated, _) = settle(blob.with_inflation(1.3), 0);
+
+    assert!((deflated - 0.7).abs() < 0.02, "deflated to {}", deflated);
+    assert!(inflated > 1.1, "inflated to {}", inflated);
+}
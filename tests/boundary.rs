@@ -0,0 +1,169 @@
+use simple_physics_engine::{
+    Boundary, Circle, Entity, Inverted, Material, Motion, Moving, Polygon, Rectangle, Resolver,
+    Segment, Transform, Vec2, World,
+};
+use std::f32::consts::FRAC_PI_2;
+
+fn assert_contact(boundary: &dyn Boundary, center: Vec2, radius: f32, normal: Vec2, depth: f32) {
+    let contact = boundary
+        .contact(center, radius)
+        .unwrap_or_else(|| panic!("expected a contact at {:?}", center));
+
+    assert!(
+        (contact.normal - normal).length() < 1e-4,
+        "normal {:?}",
+        contact.normal
+    );
+    assert!(
+        (contact.depth - depth).abs() < 1e-4,
+        "depth {}",
+        contact.depth
+    );
+}
+
+#[test]
+fn circle_pushes_entities_back_inside() {
+    let circle = Circle::new(Vec2::ZERO, 100.0);
+
+    assert!(circle.contact(Vec2::new(50.0, 0.0), 10.0).is_none());
+    assert_contact(&circle, Vec2::new(95.0, 0.0), 10.0, -Vec2::X, 5.0);
+    assert_contact(&circle, Vec2::new(0.0, 120.0), 10.0, -Vec2::Y, 30.0);
+}
+
+#[test]
+fn rectangle_pushes_entities_off_the_nearest_wall() {
+    let rectangle = Rectangle::new(Vec2::ZERO, Vec2::new(200.0, 100.0));
+
+    assert!(rectangle.contact(Vec2::new(100.0, 50.0), 10.0).is_none());
+    assert_contact(&rectangle, Vec2::new(100.0, 95.0), 10.0, -Vec2::Y, 5.0);
+    assert_contact(&rectangle, Vec2::new(-5.0, 50.0), 10.0, Vec2::X, 15.0);
+}
+
+#[test]
+fn concave_polygon_handles_both_windings() {
+    // An L shape, whose notch at the top right is outside the polygon.
+    let vertices = vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(100.0, 0.0),
+        Vec2::new(100.0, 50.0),
+        Vec2::new(50.0, 50.0),
+        Vec2::new(50.0, 100.0),
+        Vec2::new(0.0, 100.0),
+    ];
+    let reversed = vertices.iter().rev().copied().collect();
+
+    for polygon in [Polygon::new(vertices), Polygon::new(reversed)] {
+        assert!(polygon.contains(Vec2::new(25.0, 75.0)));
+        assert!(!polygon.contains(Vec2::new(75.0, 75.0)));
+        assert!(polygon.contact(Vec2::new(25.0, 25.0), 10.0).is_none());
+        assert_contact(&polygon, Vec2::new(45.0, 75.0), 10.0, -Vec2::X, 5.0);
+        assert_contact(&polygon, Vec2::new(75.0, 55.0), 10.0, -Vec2::Y, 15.0);
+    }
+}
+
+#[test]
+fn inverted_boundaries_keep_entities_outside() {
+    let obstacle = Inverted(Circle::new(Vec2::ZERO, 50.0));
+
+    assert!(!obstacle.is_container());
+    assert!(obstacle.contact(Vec2::new(70.0, 0.0), 10.0).is_none());
+    assert_contact(&obstacle, Vec2::new(55.0, 0.0), 10.0, Vec2::X, 5.0);
+    assert_contact(&obstacle, Vec2::new(0.0, -40.0), 10.0, -Vec2::Y, 20.0);
+
+    let block = Inverted(Rectangle::new(Vec2::ZERO, Vec2::splat(100.0)));
+    assert_contact(&block, Vec2::new(50.0, -5.0), 10.0, -Vec2::Y, 5.0);
+}
+
+#[test]
+fn segments_push_entities_off_either_side_and_the_ends() {
+    let segment = Segment::new(Vec2::ZERO, Vec2::new(100.0, 0.0), 2.0);
+
+    assert!(!segment.is_container());
+    assert!(segment.contact(Vec2::new(50.0, 20.0), 10.0).is_none());
+    assert!(segment.contact(Vec2::new(500.0, 0.0), 10.0).is_none());
+    assert_contact(&segment, Vec2::new(50.0, 8.0), 10.0, Vec2::Y, 4.0);
+    assert_contact(&segment, Vec2::new(50.0, -8.0), 10.0, -Vec2::Y, 4.0);
+    assert_contact(&segment, Vec2::new(110.0, 0.0), 10.0, Vec2::X, 2.0);
+}
+
+#[test]
+fn one_way_segments_only_block_from_the_solid_side() {
+    // Drawn left to right, so solid from above (negative y on screen).
+    let platform = Segment::new(Vec2::ZERO, Vec2::new(100.0, 0.0), 0.0).one_way();
+
+    assert_contact(&platform, Vec2::new(50.0, -8.0), 10.0, -Vec2::Y, 2.0);
+    assert!(platform.contact(Vec2::new(50.0, 8.0), 10.0).is_none());
+}
+
+#[test]
+fn capsules_take_the_deepest_contact_along_their_length() {
+    let rectangle = Rectangle::new(Vec2::ZERO, Vec2::new(200.0, 100.0));
+    let (start, end) = (Vec2::new(100.0, 50.0), Vec2::new(195.0, 50.0));
+    let contact = rectangle.contact_capsule(start, end, 10.0).unwrap();
+    assert!((contact.normal - -Vec2::X).length() < 1e-4);
+    assert!((contact.depth - 5.0).abs() < 1e-4);
+
+    // A ramp crossing the middle of a lying capsule, away from both ends.
+    let ramp = Segment::new(Vec2::new(50.0, 0.0), Vec2::new(50.0, 100.0), 0.0);
+    let (start, end) = (Vec2::new(0.0, 104.0), Vec2::new(100.0, 104.0));
+    let contact = ramp.contact_capsule(start, end, 10.0).unwrap();
+    assert!((contact.normal - Vec2::Y).length() < 1e-4);
+    assert!((contact.depth - 6.0).abs() < 1e-4);
+}
+
+#[test]
+fn moving_boundaries_collide_where_they_have_moved_to() {
+    let rectangle = Rectangle::from_center(Vec2::ZERO, Vec2::new(100.0, 50.0));
+    let mut moving = Moving::new(rectangle, Vec2::ZERO, |time| Transform {
+        offset: Vec2::new(100.0 * time, 0.0),
+        angle: FRAC_PI_2 * time,
+        scale: 1.0 + time,
+    });
+    moving.advance(1.0);
+
+    // Turned upright, doubled and shifted: 100 wide and 200 tall about x = 100.
+    assert!(moving.contact(Vec2::new(100.0, 80.0), 10.0).is_none());
+    assert_contact(&moving, Vec2::new(145.0, 0.0), 10.0, -Vec2::X, 5.0);
+    assert_contact(&moving, Vec2::new(100.0, 95.0), 10.0, -Vec2::Y, 5.0);
+}
+
+#[test]
+fn moving_boundaries_report_the_velocity_of_their_outline() {
+    let mut drum = Moving::spinning(Circle::new(Vec2::ZERO, 100.0), Vec2::ZERO, 2.0);
+    assert_eq!(drum.velocity_at(Vec2::new(100.0, 0.0)), Vec2::ZERO);
+
+    drum.advance(0.001);
+
+    let velocity = drum.velocity_at(Vec2::new(100.0, 0.0));
+    assert!(
+        (velocity - Vec2::new(0.0, 200.0)).length() < 0.5,
+        "velocity {}",
+        velocity
+    );
+}
+
+#[test]
+fn moving_boundaries_carry_entities_along() {
+    let ball = |x: f32, y: f32| Entity::new(10.0, Motion::new(x, y));
+
+    let box_ = Rectangle::from_center(Vec2::ZERO, Vec2::new(100.0, 100.0));
+    let sliding = Moving::new(box_, Vec2::ZERO, |time| {
+        Transform::shifted(Vec2::new(100.0 * time, 0.0))
+    });
+    let mut world = World::new(sliding, Resolver::new(0.0, 0.0)).with_entities([ball(0.0, 0.0)]);
+    for _ in 0..240 {
+        world.update(1.0 / 120.0);
+    }
+    let x = world.entities[0].borrow().motion.position.x;
+    assert!(x > 150.0, "ball left behind at {}", x);
+
+    let drum = Circle::new(Vec2::ZERO, 200.0).with_material(Material::RUBBER);
+    let spinning = Moving::spinning(drum, Vec2::ZERO, 2.0);
+    let mut world =
+        World::new(spinning, Resolver::new(0.0, 1000.0)).with_entities([ball(0.0, 190.0)]);
+    for _ in 0..120 {
+        world.update(1.0 / 120.0);
+    }
+    let x = world.entities[0].borrow().motion.position.x;
+    assert!(x < -20.0, "ball not dragged round, at {}", x);
+}
@@ -0,0 +1,182 @@
+use simple_physics_engine::{
+    Aabb, Axis, Broadphase, BruteForce, Bvh, Entity, Motion, SweepAndPrune, UniformGrid, Vec2,
+};
+use std::{cell::RefCell, collections::BTreeSet};
+
+/// Deterministic scatter of entities with a spread of radii.
+fn scatter(count: usize, extent: f32, min_radius: f32, max_radius: f32) -> Vec<RefCell<Entity>> {
+    let mut seed = 0x2545_f491_u32;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as f32 / u32::MAX as f32
+    };
+
+    (0..count)
+        .map(|_| {
+            let motion = Motion::new(next() * extent - extent * 0.5, next() * extent);
+            let radius = min_radius + next() * (max_radius - min_radius);
+            RefCell::new(Entity::new(radius, motion))
+        })
+        .collect()
+}
+
+/// Pairs reported by `broadphase` that actually overlap.
+fn contacts(
+    broadphase: &mut dyn Broadphase,
+    entities: &[RefCell<Entity>],
+) -> BTreeSet<(usize, usize)> {
+    let mut pairs = Vec::new();
+    broadphase.pairs(entities, &mut pairs);
+
+    let unique: BTreeSet<_> = pairs.iter().copied().collect();
+    assert_eq!(
+        unique.len(),
+        pairs.len(),
+        "{} reported a duplicate pair",
+        broadphase.name()
+    );
+
+    pairs
+        .into_iter()
+        .filter(|&(a, b)| {
+            assert!(a < b);
+            let (a, b) = (entities[a].borrow(), entities[b].borrow());
+            a.motion.position.distance(b.motion.position) < a.radius + b.radius
+        })
+        .collect()
+}
+
+fn assert_matches_brute_force(broadphase: &mut dyn Broadphase, entities: &[RefCell<Entity>]) {
+    let expected = contacts(&mut BruteForce, entities);
+    assert!(!expected.is_empty());
+    assert_eq!(
+        contacts(broadphase, entities),
+        expected,
+        "{}",
+        broadphase.name()
+    );
+}
+
+#[test]
+fn uniform_grid_finds_every_contact() {
+    let entities = scatter(2000, 1000.0, 2.0, 10.0);
+
+    assert_matches_brute_force(&mut UniformGrid::new(), &entities);
+    assert_matches_brute_force(&mut UniformGrid::with_cell_size(20.0), &entities);
+}
+
+#[test]
+fn uniform_grid_buckets_polygons_by_their_bounds() {
+    // Thin triangles whose centroids sit far from the middle of their bounds,
+    // pointing right when `flipped` is false and left otherwise.
+    let triangle = |x: f32, y: f32, flipped: bool| {
+        let tip = if flipped { 90.0 } else { 0.0 };
+        let vertices = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(90.0, 0.0),
+            Vec2::new(tip, 10.0),
+        ];
+        RefCell::new(Entity::polygon(vertices, Motion::new(x, y)))
+    };
+    let overlapping = |broadphase: &mut dyn Broadphase, entities: &[RefCell<Entity>]| {
+        let mut pairs = Vec::new();
+        broadphase.pairs(entities, &mut pairs);
+        pairs
+            .into_iter()
+            .filter(|&(a, b)| {
+                let (a, b) = (entities[a].borrow(), entities[b].borrow());
+                a.bounds().overlaps(&b.bounds())
+            })
+            .collect::<BTreeSet<_>>()
+    };
+
+    // Bounds 59..149 and 144..234, with centroids two cells apart.
+    let tips = [triangle(59.0, 0.0, true), triangle(144.0, 0.0, false)];
+    assert_eq!(
+        overlapping(&mut UniformGrid::new(), &tips),
+        BTreeSet::from([(0, 1)])
+    );
+
+    let rows: Vec<_> = (0..400)
+        .map(|i| triangle((i % 20) as f32 * 70.0, (i / 20) as f32 * 9.0, i % 2 == 1))
+        .collect();
+    let expected = overlapping(&mut BruteForce, &rows);
+    assert!(!expected.is_empty());
+    assert_eq!(overlapping(&mut UniformGrid::new(), &rows), expected);
+}
+
+#[test]
+fn sweep_and_prune_finds_every_contact() {
+    let entities = scatter(2000, 1000.0, 1.0, 40.0);
+
+    assert_matches_brute_force(&mut SweepAndPrune::new(Axis::X), &entities);
+    assert_matches_brute_force(&mut SweepAndPrune::new(Axis::Y), &entities);
+}
+
+#[test]
+fn sweep_and_prune_keeps_up_with_moving_entities() {
+    let entities = scatter(500, 400.0, 2.0, 12.0);
+    let mut broadphase = SweepAndPrune::default();
+
+    for step in 0..10 {
+        entities.iter().enumerate().for_each(|(i, entity)| {
+            let offset = if (i + step) % 2 == 0 { 15.0 } else { -15.0 };
+            entity.borrow_mut().motion.position.x += offset;
+        });
+
+        assert_matches_brute_force(&mut broadphase, &entities);
+    }
+}
+
+#[test]
+fn bvh_finds_every_contact_across_radius_scales() {
+    let mut entities = scatter(1500, 2000.0, 0.5, 5.0);
+    entities.extend(scatter(20, 2000.0, 50.0, 300.0));
+    let mut broadphase = Bvh::default();
+
+    for step in 0..5 {
+        entities.iter().enumerate().for_each(|(i, entity)| {
+            let offset = if (i + step) % 3 == 0 { 40.0 } else { -1.0 };
+            entity.borrow_mut().motion.position.y += offset;
+        });
+
+        assert_matches_brute_force(&mut broadphase, &entities);
+    }
+}
+
+#[test]
+fn region_queries_agree_with_a_linear_scan() {
+    let entities = scatter(1000, 1000.0, 1.0, 30.0);
+    let regions = [
+        Aabb::new(Vec2::new(-100.0, 100.0), Vec2::new(100.0, 300.0)),
+        Aabb::new(Vec2::new(-500.0, 0.0), Vec2::new(500.0, 1000.0)),
+        Aabb::new(Vec2::new(2000.0, 2000.0), Vec2::new(2100.0, 2100.0)),
+    ];
+    let mut bvh = Bvh::default();
+
+    for region in &regions {
+        let (mut expected, mut actual) = (Vec::new(), Vec::new());
+        BruteForce.query(&entities, region, &mut expected);
+        bvh.query(&entities, region, &mut actual);
+
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn uniform_grid_handles_radius_less_points() {
+    let entities: Vec<_> = (0..10)
+        .map(|i| {
+            let mut entity = Entity::new(1.0, Motion::new(i as f32 * 300.0, 5000.0));
+            entity.radius = 0.0;
+            RefCell::new(entity)
+        })
+        .collect();
+    let mut pairs = Vec::new();
+
+    UniformGrid::new().pairs(&entities, &mut pairs);
+
+    assert!(pairs.is_empty(), "{:?}", pairs);
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::{position, world};
+use simple_physics_engine::{Cloth, Vec2};
+
+#[test]
+fn cloth_links_structure_shear_and_bend() {
+    let cloth = Cloth::new(Vec2::ZERO, Vec2::new(30.0, 20.0), 4, 3).pin_top_corners();
+    let (particles, sticks) = cloth.build(0);
+
+    assert_eq!(particles.len(), 12);
+    let structural = 3 * 3 + 4 * 2;
+    let (shear, bend) = (2 * 3 * 2, 2 * 3 + 4);
+    assert_eq!(sticks.len(), structural + shear + bend);
+    assert_eq!(
+        sticks.iter().filter(|stick| stick.visible).count(),
+        structural
+    );
+
+    let pinned: Vec<_> = particles
+        .iter()
+        .enumerate()
+        .filter(|(_, particle)| particle.is_static())
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(pinned, [cloth.index(0, 0), cloth.index(3, 0)]);
+}
+
+#[test]
+fn hanging_cloth_holds_together() {
+    let mut world = world(1000.0);
+    let particles = Cloth::new(Vec2::new(-100.0, 0.0), Vec2::new(200.0, 100.0), 11, 6)
+        .with_tearing(1.5)
+        .pin_top_corners()
+        .add_to(&mut world);
+    let links = world.sticks.len();
+
+    for _ in 0..480 {
+        world.update(1.0 / 240.0);
+    }
+
+    assert_eq!(world.sticks.len(), links);
+    let (first, last) = (particles.start, particles.end - 1);
+    assert_eq!(position(&world, first), Vec2::new(-100.0, 0.0));
+    assert!(position(&world, last).y > 100.0);
+}
+
+#[test]
+fn overstretched_links_tear() {
+    let mut world = world(1000.0);
+    let cloth = Cloth::new(Vec2::ZERO, Vec2::new(100.0, 100.0), 6, 6)
+        .with_tearing(2.0)
+        .pin_top_edge();
+    cloth.add_to(&mut world);
+    let corner = cloth.index(5, 5);
+    let links = world.sticks.len();
+
+    world.entities[corner].borrow_mut().motion.position += Vec2::new(0.0, 500.0);
+    world.update(1.0 / 240.0);
+
+    assert!(world.sticks.len() < links);
+    assert!(world
+        .sticks
+        .iter()
+        .all(|stick| stick.a != corner && stick.b != corner));
+}
@@ -0,0 +1,92 @@
+use simple_physics_engine::{Circle, Cluster, Entity, Motion, Resolver, Vec2, World};
+
+/// A square of four particles held by a cluster in a world without gravity.
+fn square(configure: fn(Cluster) -> Cluster) -> World {
+    let mut world = World::new(Circle::new(Vec2::ZERO, 1000.0), Resolver::new(0.0, 0.0));
+    for &(x, y) in &[(-20.0, -20.0), (20.0, -20.0), (20.0, 20.0), (-20.0, 20.0)] {
+        world.add_entity(Entity::new(5.0, Motion::new(x, y)).with_group(0));
+    }
+    let cluster = configure(Cluster::new(&world.entities, 0..4));
+    world.add_cluster(cluster);
+    world
+}
+
+/// Moves the first particle of `world` by `offset` without giving it speed.
+fn knock(world: &World, offset: Vec2) {
+    let mut entity = world.entities[0].borrow_mut();
+    entity.motion.position += offset;
+    entity.motion.previous_position += offset;
+}
+
+fn gap(world: &World) -> f32 {
+    let position = |index: usize| world.entities[index].borrow().motion.position;
+    position(0).distance(position(2))
+}
+
+#[test]
+fn fits_the_rest_shape_moved_and_turned() {
+    let world = square(|cluster| cluster);
+    let (shift, angle) = (Vec2::new(100.0, -50.0), 0.6_f32);
+    for entity in &world.entities {
+        let mut entity = entity.borrow_mut();
+        let p = entity.motion.position;
+        let turned = Vec2::new(
+            p.x * angle.cos() - p.y * angle.sin(),
+            p.x * angle.sin() + p.y * angle.cos(),
+        );
+        entity.motion.position = turned + shift;
+    }
+
+    let (center, fitted) = world.clusters[0].fit(&world.entities);
+
+    assert!(center.distance(shift) < 1e-3, "centre at {}", center);
+    assert!((fitted - angle).abs() < 1e-4, "angle {}", fitted);
+}
+
+#[test]
+fn elastic_clusters_spring_back_after_a_knock() {
+    let mut world = square(|cluster| cluster.with_stiffness(0.5));
+    let rest = gap(&world);
+    knock(&world, Vec2::new(-15.0, -10.0));
+
+    for _ in 0..240 {
+        world.update(1.0 / 120.0);
+    }
+
+    assert!(
+        (gap(&world) - rest).abs() < 0.5,
+        "gap {} of {}",
+        gap(&world),
+        rest
+    );
+}
+
+#[test]
+fn loose_clusters_stay_where_they_are_knocked() {
+    let mut world = square(|cluster| cluster.with_stiffness(0.0));
+    let knocked = {
+        knock(&world, Vec2::new(-15.0, -10.0));
+        gap(&world)
+    };
+
+    for _ in 0..60 {
+        world.update(1.0 / 120.0);
+    }
+
+    assert!((gap(&world) - knocked).abs() < 1e-3);
+}
+
+#[test]
+fn plastic_clusters_keep_their_dents() {
+    let mut world = square(|cluster| cluster.with_stiffness(0.5).with_plasticity(2.0, 1.0));
+    let rest = gap(&world);
+    knock(&world, Vec2::new(-15.0, -10.0));
+
+    for _ in 0..240 {
+        world.update(1.0 / 120.0);
+    }
+
+    assert!(gap(&world) - rest > 5.0, "gap {} of {}", gap(&world), rest);
+    let offsets = &world.clusters[0].rest;
+    assert!(offsets[0].distance(offsets[2]) - rest > 5.0);
+}
@@ -0,0 +1,22 @@
+//! Fixtures shared by the integration tests. Each test crate uses only some
+//! of them.
+#![allow(dead_code)]
+
+use simple_physics_engine::{Circle, Resolver, Vec2, World};
+
+/// An empty world inside a circle wide enough to stay out of the way.
+pub fn world(gravity: f32) -> World {
+    World::new(Circle::new(Vec2::ZERO, 1000.0), Resolver::new(0.0, gravity))
+}
+
+pub fn position(world: &World, index: usize) -> Vec2 {
+    world.entities[index].borrow().motion.position
+}
+
+pub fn positions(world: &World) -> Vec<Vec2> {
+    world
+        .entities
+        .iter()
+        .map(|entity| entity.borrow().motion.position)
+        .collect()
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::world;
+use simple_physics_engine::{Entity, Headless, Motion, Statistics, Vec2};
+
+#[test]
+fn runs_the_requested_steps_and_reports_where_entities_ended_up() {
+    let mut world = world(1000.0).with_entities([
+        Entity::new(10.0, Motion::new(-100.0, 0.0)),
+        Entity::new(10.0, Motion::new(100.0, 0.0)),
+    ]);
+
+    let report = Headless::new(30, 0.01).run(&mut world);
+
+    assert_eq!((report.steps, report.dt), (30, 0.01));
+    assert_eq!(report.positions.len(), 2);
+    for (i, position) in report.positions.iter().enumerate() {
+        assert_eq!(*position, world.entities[i].borrow().motion.position);
+        assert!(position.y > 40.0, "entity {} did not fall", i);
+    }
+    assert_eq!(report.statistics.entity_count, 2);
+}
+
+#[test]
+fn writes_one_csv_row_per_entity() {
+    let mut world = world(0.0).with_entities([
+        Entity::new(10.0, Motion::new(1.5, -2.0)),
+        Entity::new(10.0, Motion::new(30.0, 40.0)),
+    ]);
+    let report = Headless::new(1, 0.01).run(&mut world);
+
+    let mut csv = Vec::new();
+    report.write_positions(&mut csv).unwrap();
+
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "index,x,y\n0,1.5,-2\n1,30,40\n"
+    );
+}
+
+#[test]
+fn statistics_of_an_empty_world_are_zero() {
+    let statistics = Statistics::new(&world(1000.0), 0.01);
+
+    assert_eq!(statistics.entity_count, 0);
+    assert_eq!(statistics.centroid, Vec2::ZERO);
+    assert_eq!((statistics.min, statistics.max), (Vec2::ZERO, Vec2::ZERO));
+    assert_eq!(statistics.mean_speed, 0.0);
+    assert_eq!(statistics.kinetic_energy, 0.0);
+}
+
+#[test]
+fn statistics_leave_static_entities_out_of_the_energy() {
+    let dt = 0.01;
+    let mut moving = Motion::new(100.0, 50.0);
+    moving.set_velocity(Vec2::new(30.0, 40.0), dt);
+    let world = world(0.0).with_entities([
+        Entity::new(10.0, moving).with_mass(2.0),
+        Entity::new(10.0, Motion::new(-100.0, -50.0)).pinned(),
+    ]);
+
+    let statistics = Statistics::new(&world, dt);
+
+    assert_eq!(statistics.entity_count, 2);
+    assert_eq!(statistics.centroid, Vec2::ZERO);
+    assert_eq!(statistics.min, Vec2::new(-100.0, -50.0));
+    assert_eq!(statistics.max, Vec2::new(100.0, 50.0));
+    assert!((statistics.max_speed - 50.0).abs() < 1e-3);
+    assert!((statistics.mean_speed - 25.0).abs() < 1e-3);
+    assert!((statistics.kinetic_energy - 2500.0).abs() < 0.5);
+}
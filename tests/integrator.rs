@@ -0,0 +1,49 @@
+use simple_physics_engine::{Euler, Integrator, Motion, Rk4, SemiImplicitEuler, Vec2, Verlet};
+
+fn fall(integrator: &dyn Integrator, steps: usize, dt: f32) -> Motion {
+    let mut motion = Motion::new(0.0, 0.0);
+    motion.set_velocity(Vec2::new(100.0, 0.0), dt);
+
+    for _ in 0..steps {
+        motion.accelerate(Vec2::new(0.0, 1000.0));
+        integrator.integrate(&mut motion, &|_, _| Vec2::ZERO, dt);
+    }
+
+    motion
+}
+
+fn error(integrator: &dyn Integrator) -> f32 {
+    let (steps, dt) = (60, 1.0 / 60.0);
+    let time = steps as f32 * dt;
+    let expected = Vec2::new(100.0 * time, 500.0 * time * time);
+
+    (fall(integrator, steps, dt).position - expected).length()
+}
+
+#[test]
+fn second_order_schemes_are_exact_under_constant_acceleration() {
+    assert!(error(&Verlet) < 1e-2);
+    assert!(error(&Rk4) < 1e-2);
+}
+
+#[test]
+fn first_order_schemes_drift_by_half_a_step() {
+    // Each Euler variant is off by `g * dt * t / 2`, in opposite directions.
+    let expected = 1000.0 * (1.0 / 60.0) * 0.5;
+
+    assert!((error(&Euler) - expected).abs() < 1e-1);
+    assert!((error(&SemiImplicitEuler) - expected).abs() < 1e-1);
+}
+
+#[test]
+fn rk4_integrates_state_dependent_acceleration() {
+    // Undamped spring: x'' = -x, so x(t) = cos(t) when starting at rest at 1.
+    let dt = 0.01;
+    let mut motion = Motion::new(1.0, 0.0);
+
+    for _ in 0..300 {
+        Rk4.integrate(&mut motion, &|position, _| -position, dt);
+    }
+
+    assert!((motion.position.x - 3.0f32.cos()).abs() < 1e-3);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::{position, world};
+use simple_physics_engine::{Entity, Motion, Stick, Vec2};
+
+#[test]
+fn pinned_entities_hold_under_load() {
+    let mut world = world(1000.0);
+    world.add_entity(Entity::new(10.0, Motion::new(0.0, 0.0)).pinned());
+    world.add_entity(Entity::new(10.0, Motion::new(100.0, 0.0)));
+    world.add_entity(Entity::new(10.0, Motion::new(0.0, -100.0)));
+    world.add_stick(Stick::new(0, 1, 100.0));
+
+    for _ in 0..240 {
+        world.update(1.0 / 120.0);
+    }
+
+    assert_eq!(position(&world, 0), Vec2::ZERO);
+    assert!(position(&world, 1).y > 50.0, "load did not swing down");
+}
+
+#[test]
+fn kinematic_entities_follow_their_path_whatever_they_hit() {
+    let mut world = world(1000.0);
+    let path = |time: f32| (Vec2::new(time * 100.0, 0.0), time);
+    world.add_entity(
+        Entity::capsule(10.0, Vec2::new(50.0, 0.0), Motion::new(0.0, 0.0)).with_path(path),
+    );
+    for i in 0..5 {
+        world.add_entity(Entity::new(
+            10.0,
+            Motion::new(-40.0 + 20.0 * i as f32, -30.0),
+        ));
+    }
+
+    let dt = 1.0 / 120.0;
+    for step in 1..=120 {
+        world.update(dt);
+        let entity = world.entities[0].borrow();
+        let (position, angle) = path(step as f32 * dt);
+        assert!(entity.motion.position.distance(position) < 1e-3);
+        assert!((entity.motion.angle - angle).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn kinematic_entities_push_what_they_hit() {
+    let mut world = world(0.0);
+    let speed = 200.0;
+    world.add_entity(
+        Entity::rectangle(Vec2::new(20.0, 100.0), Motion::new(0.0, 0.0))
+            .with_path(move |time: f32| (Vec2::new(-100.0 + time * speed, 0.0), 0.0)),
+    );
+    world.add_entity(Entity::new(10.0, Motion::new(0.0, 0.0)));
+
+    let dt = 1.0 / 120.0;
+    for _ in 0..120 {
+        world.update(dt);
+    }
+
+    let ball = world.entities[1].borrow();
+    let paddle = world.entities[0].borrow();
+    assert!(ball.motion.position.x >= paddle.motion.position.x + 19.0);
+    assert!(
+        ball.motion.velocity(dt).x >= speed * 0.99,
+        "ball at {}",
+        ball.motion.velocity(dt)
+    );
+    assert!(paddle.motion.velocity(dt).distance(Vec2::new(speed, 0.0)) < 1e-2);
+}
@@ -0,0 +1,72 @@
+use simple_physics_engine::{Motion, Vec2};
+
+fn gravity() -> Vec2 {
+    Vec2::new(0.0, 1000.0)
+}
+const TOLERANCE: f32 = 1e-2;
+
+fn simulate(motion: &mut Motion, steps: &[f32]) -> f32 {
+    steps.iter().fold(0.0, |time, &dt| {
+        motion.accelerate(gravity());
+        motion.update_position(dt);
+        time + dt
+    })
+}
+
+fn assert_close(actual: Vec2, expected: Vec2) {
+    let error = (actual - expected).length();
+    assert!(
+        error < TOLERANCE * expected.length().max(1.0),
+        "expected {:?}, got {:?} (error {})",
+        expected,
+        actual,
+        error
+    );
+}
+
+#[test]
+fn free_fall_matches_analytic_solution() {
+    let mut motion = Motion::new(0.0, 0.0);
+    let time = simulate(&mut motion, &[1.0 / 60.0; 120]);
+
+    assert_close(motion.position, 0.5 * gravity() * time * time);
+    assert_close(motion.velocity(1.0 / 60.0), gravity() * (time - 0.5 / 60.0));
+}
+
+#[test]
+fn projectile_matches_analytic_solution() {
+    let dt = 1.0 / 120.0;
+    let initial_velocity = Vec2::new(300.0, -600.0);
+    let mut motion = Motion::new(10.0, 20.0);
+    motion.set_velocity(initial_velocity, dt);
+
+    let time = simulate(&mut motion, &[dt; 90]);
+    let expected = Vec2::new(10.0, 20.0) + initial_velocity * time + 0.5 * gravity() * time * time;
+
+    assert_close(motion.position, expected);
+}
+
+#[test]
+fn variable_steps_match_analytic_solution() {
+    let steps: Vec<f32> = (0..100)
+        .map(|i| match i % 3 {
+            0 => 1.0 / 30.0,
+            1 => 1.0 / 144.0,
+            _ => 1.0 / 60.0,
+        })
+        .collect();
+    let mut motion = Motion::new(0.0, 0.0);
+    let time = simulate(&mut motion, &steps);
+
+    assert_close(motion.position, 0.5 * gravity() * time * time);
+}
+
+#[test]
+fn result_is_independent_of_step_size() {
+    let mut coarse = Motion::new(0.0, 0.0);
+    let mut fine = Motion::new(0.0, 0.0);
+    simulate(&mut coarse, &[1.0 / 30.0; 30]);
+    simulate(&mut fine, &[1.0 / 240.0; 240]);
+
+    assert_close(coarse.position, fine.position);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::position;
+use simple_physics_engine::{
+    Broadphase, BruteForce, Bvh, Entity, Motion, Periodic, Resolver, SweepAndPrune, UniformGrid,
+    Vec2, World,
+};
+
+fn domain() -> Periodic {
+    Periodic::new(Vec2::ZERO, Vec2::new(200.0, 100.0))
+}
+
+#[test]
+fn minimum_images_take_the_short_way_round() {
+    let domain = domain();
+
+    assert_eq!(
+        domain.minimum_image(Vec2::new(10.0, 50.0), Vec2::new(190.0, 50.0)),
+        Vec2::new(-20.0, 0.0)
+    );
+    assert_eq!(
+        domain.minimum_image(Vec2::new(50.0, 95.0), Vec2::new(60.0, 5.0)),
+        Vec2::new(10.0, 10.0)
+    );
+    assert_eq!(
+        domain.image_offset(Vec2::new(10.0, 50.0), Vec2::new(190.0, 50.0)),
+        Vec2::new(-200.0, 0.0)
+    );
+    assert_eq!(domain.wrap(Vec2::new(-30.0, 250.0)), Vec2::new(170.0, 50.0));
+}
+
+#[test]
+fn entities_leaving_one_edge_come_back_at_the_other() {
+    let mut world = World::periodic(domain(), Resolver::new(0.0, 0.0));
+    let mut motion = Motion::new(190.0, 95.0);
+    let dt = 1.0 / 100.0;
+    motion.set_velocity(Vec2::new(500.0, 300.0), dt);
+    world.add_entity(Entity::new(5.0, motion));
+
+    for _ in 0..10 {
+        world.update(dt);
+    }
+
+    let position = position(&world, 0);
+    assert!(
+        (position - Vec2::new(40.0, 25.0)).length() < 1e-2,
+        "at {}",
+        position
+    );
+    let velocity = world.entities[0].borrow().motion.velocity(dt);
+    assert!((velocity - Vec2::new(500.0, 300.0)).length() < 1e-2);
+}
+
+#[test]
+fn entities_collide_across_the_wrap_with_any_broadphase() {
+    let broadphases: [Box<dyn Broadphase>; 4] = [
+        Box::new(BruteForce),
+        Box::new(UniformGrid::new()),
+        Box::new(SweepAndPrune::default()),
+        Box::new(Bvh::default()),
+    ];
+
+    for broadphase in broadphases {
+        let name = broadphase.name();
+        let mut resolver = Resolver::new(0.0, 0.0);
+        resolver.broadphase = broadphase;
+        // Overlapping by 6 across the left and right edges, and by 4 across
+        // the top and bottom.
+        let mut world = World::periodic(domain(), resolver).with_entities([
+            Entity::new(10.0, Motion::new(7.0, 30.0)),
+            Entity::new(10.0, Motion::new(193.0, 30.0)),
+            Entity::new(10.0, Motion::new(100.0, 2.0)),
+            Entity::new(10.0, Motion::new(100.0, 86.0)),
+        ]);
+
+        world.update(1.0 / 60.0);
+
+        let gap = |a: usize, b: usize| {
+            domain()
+                .minimum_image(position(&world, a), position(&world, b))
+                .length()
+        };
+        assert!(gap(0, 1) > 19.9, "{}: gap {}", name, gap(0, 1));
+        assert!(gap(2, 3) > 19.9, "{}: gap {}", name, gap(2, 3));
+        assert!(position(&world, 0).x > 7.0, "{}: pushed the long way", name);
+    }
+}
@@ -0,0 +1,340 @@
+use simple_physics_engine::{
+    Circle, Drag, Entity, Integrator, Inverted, Material, Motion, Rectangle, Resolver, Rk4,
+    Segment, Vec2, Verlet, World,
+};
+
+fn world(entities: impl IntoIterator<Item = Entity>) -> World {
+    let boundary = Circle::new(Vec2::ZERO, 1000.0);
+    World::new(boundary, Resolver::new(0.0, 0.0)).with_entities(entities)
+}
+
+fn distance(world: &World, a: usize, b: usize) -> f32 {
+    let (a, b) = (world.entities[a].borrow(), world.entities[b].borrow());
+    a.motion.position.distance(b.motion.position)
+}
+
+#[test]
+fn collisions_separate_entities_by_the_sum_of_their_radii() {
+    let mut world = world([
+        Entity::new(10.0, Motion::new(0.0, 0.0)),
+        Entity::new(30.0, Motion::new(35.0, 0.0)),
+        Entity::new(5.0, Motion::new(100.0, 0.0)),
+    ]);
+
+    world.update(1.0 / 60.0);
+
+    assert!(distance(&world, 0, 1) >= 40.0 - 1e-3);
+    assert_eq!(
+        world.entities[2].borrow().motion.position,
+        Vec2::new(100.0, 0.0)
+    );
+}
+
+#[test]
+fn boundary_keeps_each_entity_inside_by_its_own_radius() {
+    let mut world = world([
+        Entity::new(10.0, Motion::new(995.0, 0.0)),
+        Entity::new(50.0, Motion::new(0.0, -980.0)),
+    ]);
+
+    world.update(1.0 / 60.0);
+
+    for entity in &world.entities {
+        let entity = entity.borrow();
+        assert!(entity.motion.position.length() + entity.radius <= 1000.0 + 1e-3);
+    }
+}
+
+#[test]
+fn every_boundary_in_the_world_is_respected() {
+    let mut world = world([
+        Entity::new(10.0, Motion::new(0.0, 0.0)),
+        Entity::new(10.0, Motion::new(200.0, 5.0)),
+    ])
+    .with_boundary(Inverted(Circle::new(Vec2::new(5.0, 0.0), 10.0)))
+    .with_boundary(Segment::new(
+        Vec2::new(100.0, 0.0),
+        Vec2::new(300.0, 0.0),
+        0.0,
+    ));
+
+    world.update(1.0 / 60.0);
+
+    let (peg, ramp) = (world.entities[0].borrow(), world.entities[1].borrow());
+    assert!(peg.motion.position.distance(Vec2::new(5.0, 0.0)) >= 20.0 - 1e-3);
+    assert!(ramp.motion.position.y >= 10.0 - 1e-3);
+}
+
+#[test]
+fn capsules_collide_along_their_whole_length() {
+    let mut world = world([
+        Entity::capsule(5.0, Vec2::new(100.0, 0.0), Motion::new(0.0, 0.0)),
+        Entity::new(10.0, Motion::new(60.0, 8.0)),
+        Entity::capsule(5.0, Vec2::new(0.0, 50.0), Motion::new(-60.0, -52.0)),
+    ]);
+
+    world.update(1.0 / 60.0);
+
+    let plank = world.entities[0].borrow();
+    for other in [1, 2] {
+        let other = world.entities[other].borrow();
+        let ((a0, a1), (b0, b1)) = (plank.segment(), other.segment());
+        let gap = (0..=100)
+            .map(|i| a0.lerp(a1, i as f32 / 100.0))
+            .flat_map(|p| (0..=100).map(move |j| p.distance(b0.lerp(b1, j as f32 / 100.0))))
+            .fold(f32::INFINITY, f32::min);
+        assert!(gap >= plank.radius + other.radius - 0.1, "gap {}", gap);
+    }
+}
+
+fn on_floor(entities: impl IntoIterator<Item = Entity>, seconds: f32) -> World {
+    let floor = Rectangle::new(Vec2::new(-1000.0, -1000.0), Vec2::new(1000.0, 0.0));
+    let mut world = World::new(floor, Resolver::new(0.0, 1000.0)).with_entities(entities);
+    for _ in 0..(seconds * 240.0) as usize {
+        world.update(1.0 / 240.0);
+    }
+    world
+}
+
+fn tilted(mut entity: Entity, angle: f32) -> Entity {
+    entity.motion.angle = angle;
+    entity.motion.previous_angle = angle;
+    entity
+}
+
+#[test]
+fn boxes_stack_even_when_offset() {
+    let world = on_floor(
+        (0..4).map(|i| {
+            let x = if i % 2 == 0 { 0.0 } else { 8.0 };
+            Entity::rectangle(
+                Vec2::new(40.0, 40.0),
+                Motion::new(x, -21.0 - 41.0 * i as f32),
+            )
+        }),
+        5.0,
+    );
+
+    for (i, entity) in world.entities.iter().enumerate() {
+        let entity = entity.borrow();
+        assert!(
+            entity.motion.angle.abs() < 0.01,
+            "angle {}",
+            entity.motion.angle
+        );
+        assert!((entity.motion.position.y - (-20.0 - 40.0 * i as f32)).abs() < 1.0);
+    }
+}
+
+#[test]
+fn boxes_come_to_rest_on_pegs_narrower_than_their_faces() {
+    let peg = Inverted(Circle::new(Vec2::ZERO, 10.0));
+    let mut world = World::new(Circle::new(Vec2::ZERO, 1000.0), Resolver::new(0.0, 1000.0))
+        .with_boundary(peg)
+        .with_entities([Entity::rectangle(
+            Vec2::new(80.0, 40.0),
+            Motion::new(0.0, -100.0),
+        )]);
+
+    for _ in 0..240 {
+        world.update(1.0 / 240.0);
+    }
+
+    let entity = world.entities[0].borrow();
+    assert!(
+        entity.motion.angle.abs() < 0.01,
+        "angle {}",
+        entity.motion.angle
+    );
+    assert!(
+        (entity.motion.position.y - -30.0).abs() < 1.0,
+        "box at {}",
+        entity.motion.position
+    );
+}
+
+#[test]
+fn boxes_topple_only_past_their_tipping_point() {
+    let world = on_floor(
+        [
+            tilted(
+                Entity::rectangle(Vec2::new(20.0, 100.0), Motion::new(0.0, -52.0)),
+                0.3,
+            ),
+            tilted(
+                Entity::rectangle(Vec2::new(20.0, 100.0), Motion::new(200.0, -52.0)),
+                0.05,
+            ),
+        ],
+        5.0,
+    );
+
+    let (fallen, upright) = (world.entities[0].borrow(), world.entities[1].borrow());
+    assert!((fallen.motion.angle.abs() - std::f32::consts::FRAC_PI_2).abs() < 0.01);
+    assert!((fallen.motion.position.y - -10.0).abs() < 0.5);
+    assert!(upright.motion.angle.abs() < 0.01);
+    assert!((upright.motion.position.y - -50.0).abs() < 0.5);
+}
+
+#[test]
+fn off_centre_hits_spin_polygons() {
+    let mut world = world([
+        Entity::rectangle(Vec2::new(20.0, 100.0), Motion::new(0.0, 0.0)),
+        Entity::new(5.0, Motion::new(-20.0, 40.0)),
+    ]);
+    world.entities[1]
+        .borrow_mut()
+        .motion
+        .set_velocity(Vec2::new(600.0, 0.0), 1.0 / 60.0);
+
+    for _ in 0..10 {
+        world.update(1.0 / 60.0);
+    }
+
+    let plank = world.entities[0].borrow();
+    assert!(plank.motion.angular_velocity(1.0 / 60.0) < 0.0);
+    assert!(plank.motion.velocity(1.0 / 60.0).x > 0.0);
+}
+
+#[test]
+fn heavier_entities_take_less_of_the_correction() {
+    let mut world = world([
+        Entity::new(10.0, Motion::new(0.0, 0.0)).with_mass(9.0),
+        Entity::new(10.0, Motion::new(10.0, 0.0)).with_mass(1.0),
+    ]);
+
+    world.update(1.0 / 60.0);
+
+    let heavy = world.entities[0].borrow().motion.previous_position;
+    let light = world.entities[1].borrow().motion.previous_position;
+    assert!((heavy.x - -1.0).abs() < 1e-4);
+    assert!((light.x - 19.0).abs() < 1e-4);
+}
+
+#[test]
+fn static_entities_are_never_moved() {
+    let mut world = world([
+        Entity::new(20.0, Motion::new(0.0, 0.0)).with_mass(f32::INFINITY),
+        Entity::new(10.0, Motion::new(0.0, -25.0)),
+    ]);
+    world.resolver.gravity = Vec2::new(0.0, 1000.0);
+
+    for _ in 0..120 {
+        world.update(1.0 / 120.0);
+    }
+
+    assert_eq!(world.entities[0].borrow().motion.position, Vec2::ZERO);
+    assert!(distance(&world, 0, 1) >= 30.0 - 1.0);
+}
+
+fn drop_height(restitution: f32) -> f32 {
+    let material = Material::new(restitution, 0.0);
+    let boundary = Circle::new(Vec2::ZERO, 400.0).with_material(material);
+    let entity = Entity::new(20.0, Motion::new(0.0, 0.0)).with_material(material);
+    let mut world = World::new(boundary, Resolver::default()).with_entities([entity]);
+    let floor = 380.0;
+
+    // Fall once, then track the highest point of the first rebound.
+    let mut lowest = 0.0f32;
+    let mut peak = floor;
+    for _ in 0..480 {
+        world.update(1.0 / 240.0);
+        let y = world.entities[0].borrow().motion.position.y;
+        lowest = lowest.max(y);
+        if lowest > floor - 1.0 {
+            peak = peak.min(y);
+        }
+    }
+
+    floor - peak
+}
+
+#[test]
+fn restitution_controls_rebound_height() {
+    // A bounce keeps `e` of the impact speed, so `e²` of the drop height.
+    assert!(drop_height(0.0) < 1.0);
+    assert!((drop_height(0.5) - 95.0).abs() < 5.0);
+    assert!((drop_height(0.9) - 307.8).abs() < 10.0);
+}
+
+/// Speed and rim speed from spinning of a ball launched along the floor.
+fn slide(material: Material) -> (f32, f32) {
+    let floor =
+        Rectangle::new(Vec2::new(-1000.0, -1000.0), Vec2::new(1000.0, 0.0)).with_material(material);
+    let mut entity = Entity::new(20.0, Motion::new(0.0, -20.0)).with_material(material);
+    entity
+        .motion
+        .set_velocity(Vec2::new(200.0, 0.0), 1.0 / 240.0);
+    let mut world = World::new(floor, Resolver::default()).with_entities([entity]);
+
+    for _ in 0..120 {
+        world.update(1.0 / 240.0);
+    }
+
+    let entity = world.entities[0].borrow();
+    (
+        entity.motion.velocity(1.0 / 240.0).length(),
+        entity.motion.angular_velocity(1.0 / 240.0) * entity.radius,
+    )
+}
+
+#[test]
+fn friction_turns_sliding_into_rolling() {
+    let ((ice, ice_rim), (sand, sand_rim)) = (slide(Material::ICE), slide(Material::SAND));
+
+    assert!(ice > 150.0, "ice slowed down to {}", ice);
+    assert!(ice_rim < ice * 0.5, "ice spun up to {} at {}", ice_rim, ice);
+    // Rolling without slipping keeps 2/3 of the speed for a uniform disc.
+    assert!((sand - 133.3).abs() < 10.0, "sand kept {}", sand);
+    assert!(
+        (sand_rim - sand).abs() < 5.0,
+        "sand rim {} at {}",
+        sand_rim,
+        sand
+    );
+}
+
+fn terminal_speed(integrator: impl Integrator + 'static, drag: Drag) -> f32 {
+    let boundary = Circle::new(Vec2::ZERO, 1e7);
+    let resolver = Resolver::default()
+        .with_drag(drag)
+        .with_integrator(integrator);
+    let mut world =
+        World::new(boundary, resolver).with_entities([Entity::new(1.0, Motion::new(0.0, 0.0))]);
+
+    for _ in 0..1200 {
+        world.update(1.0 / 120.0);
+    }
+
+    let entity = world.entities[0].borrow();
+    entity.motion.velocity(1.0 / 120.0).y
+}
+
+#[test]
+fn drag_limits_falling_speed_to_terminal_velocity() {
+    // Gravity is 1000, so linear drag of 2 settles at 500 and quadratic drag
+    // of 0.01 at sqrt(1000 / 0.01) = 316.
+    for (drag, expected) in [(Drag::new(2.0, 0.0), 500.0), (Drag::new(0.0, 0.01), 316.23)] {
+        assert!((terminal_speed(Verlet, drag) - expected).abs() < expected * 0.01);
+        assert!((terminal_speed(Rk4, drag) - expected).abs() < expected * 0.01);
+    }
+}
+
+#[test]
+fn entity_drag_overrides_the_resolver() {
+    let boundary = Circle::new(Vec2::ZERO, 1e7);
+    let resolver = Resolver::default().with_drag(Drag::new(10.0, 0.0));
+    let mut world = World::new(boundary, resolver).with_entities([
+        Entity::new(1.0, Motion::new(0.0, 0.0)),
+        Entity::new(1.0, Motion::new(100.0, 0.0)).with_drag(Drag::NONE),
+    ]);
+
+    for _ in 0..60 {
+        world.update(1.0 / 60.0);
+    }
+
+    // With linear drag `k`, y(t) = g / k * (t - (1 - e^(-kt)) / k), so 90.
+    let (damped, free) = (world.entities[0].borrow(), world.entities[1].borrow());
+    assert!((damped.motion.position.y - 90.0).abs() < 2.0);
+    assert!((free.motion.position.y - 500.0).abs() < 1.0);
+}
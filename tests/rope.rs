@@ -0,0 +1,71 @@
+mod common;
+
+use common::{position, world};
+use simple_physics_engine::{Cloth, Entity, Motion, Rope, Vec2, DEFAULT_DENSITY};
+
+#[test]
+fn ropes_link_their_ends_and_hang_from_pins() {
+    let mut world = world(1000.0);
+    let (start, end) = (Vec2::new(-200.0, 0.0), Vec2::new(200.0, 0.0));
+    let bridge = Rope::new(start, end, 11)
+        .with_length(500.0)
+        .pin_start()
+        .pin_end()
+        .add_to(&mut world);
+    let hanging = Rope::new(Vec2::new(-100.0, -500.0), Vec2::new(100.0, -500.0), 5)
+        .pin_start()
+        .add_to(&mut world);
+
+    assert_eq!(bridge, 0..11);
+    assert_eq!(hanging, 11..16);
+    assert_eq!(world.sticks.len(), 10 + 4);
+
+    for _ in 0..600 {
+        world.update(1.0 / 120.0);
+    }
+
+    assert_eq!(position(&world, 0), start);
+    assert_eq!(position(&world, 10), end);
+    assert!(position(&world, 5).y > 100.0, "bridge did not sag");
+    let tail = position(&world, 15) - position(&world, 11);
+    assert!(tail.y > 100.0 && tail.length() < 210.0, "tail at {}", tail);
+}
+
+#[test]
+fn only_self_colliding_ropes_push_their_own_links_apart() {
+    let spread = |rope: Rope| {
+        let mut world = world(0.0);
+        rope.add_to(&mut world);
+        world.update(1.0 / 60.0);
+        position(&world, 0).distance(position(&world, 1))
+    };
+    let rope = Rope::new(Vec2::new(-10.0, 0.0), Vec2::new(10.0, 0.0), 3).with_radius(8.0);
+
+    assert!((spread(rope) - 10.0).abs() < 1e-3);
+    assert!(spread(rope.self_colliding()) > 12.0);
+}
+
+#[test]
+fn radius_less_links_weigh_a_unit_of_area() {
+    let point = Entity::new(0.0, Motion::new(0.0, 0.0));
+    assert_eq!(point.mass(), DEFAULT_DENSITY);
+
+    let mut world = world(1000.0);
+    let rope = Rope::new(Vec2::new(-100.0, 0.0), Vec2::new(100.0, 0.0), 5)
+        .with_radius(0.0)
+        .pin_start()
+        .add_to(&mut world);
+    let cloth = Cloth::new(Vec2::new(-50.0, -300.0), Vec2::new(100.0, 100.0), 4, 4)
+        .with_radius(0.0)
+        .add_to(&mut world);
+
+    for _ in 0..120 {
+        world.update(1.0 / 120.0);
+    }
+
+    for i in rope.chain(cloth) {
+        let position = position(&world, i);
+        assert!(position.is_finite(), "link {} at {}", i, position);
+    }
+    assert!(position(&world, 4).y > 50.0, "rope did not swing down");
+}
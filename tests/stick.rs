@@ -0,0 +1,89 @@
+use simple_physics_engine::{Circle, Entity, Motion, Resolver, Stick, Vec2, World};
+
+fn world(
+    entities: impl IntoIterator<Item = Entity>,
+    sticks: impl IntoIterator<Item = Stick>,
+) -> World {
+    World::new(Circle::new(Vec2::ZERO, 1000.0), Resolver::default())
+        .with_entities(entities)
+        .with_sticks(sticks)
+}
+
+fn pivot() -> Entity {
+    Entity::new(5.0, Motion::new(0.0, 0.0)).with_mass(f32::INFINITY)
+}
+
+fn length(world: &World, stick: usize) -> f32 {
+    let (start, end) = world.sticks[stick].ends(&world.entities);
+    start.distance(end)
+}
+
+#[test]
+fn rigid_sticks_hold_their_length_while_swinging() {
+    let mut world = world(
+        [pivot(), Entity::new(10.0, Motion::new(200.0, 0.0))],
+        [Stick::new(0, 1, 200.0)],
+    );
+
+    let mut leftmost = f32::INFINITY;
+    for _ in 0..240 {
+        world.update(1.0 / 120.0);
+        assert!((length(&world, 0) - 200.0).abs() < 1.0);
+        leftmost = leftmost.min(world.entities[1].borrow().motion.position.x);
+    }
+
+    assert_eq!(world.entities[0].borrow().motion.position, Vec2::ZERO);
+    assert!(leftmost < -150.0, "only swung to {}", leftmost);
+}
+
+/// Furthest a ball dropped on a stick of `stiffness` stretches it.
+fn stretch(stiffness: f32, dt: f32) -> f32 {
+    let mut world = world(
+        [pivot(), Entity::new(10.0, Motion::new(0.0, 100.0))],
+        [Stick::new(0, 1, 100.0).with_stiffness(stiffness)],
+    );
+    (0..(1.0 / dt) as usize)
+        .map(|_| {
+            world.update(dt);
+            length(&world, 0) - 100.0
+        })
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn soft_sticks_stretch_like_springs_at_any_step_size() {
+    // Released at rest length, a spring overshoots to twice its sag `mg/k`.
+    let mass = Entity::new(10.0, Motion::new(0.0, 0.0)).mass();
+    let expected = 2.0 * mass * 1000.0 / 10000.0;
+
+    assert!(stretch(f32::INFINITY, 1.0 / 120.0) < 1.0);
+    for dt in [1.0 / 60.0, 1.0 / 240.0] {
+        let soft = stretch(10000.0, dt);
+        assert!(
+            (soft - expected).abs() < expected * 0.1,
+            "stretched {} at {}",
+            soft,
+            dt
+        );
+    }
+}
+
+#[test]
+fn anchors_hang_bodies_from_a_point_on_them() {
+    let corner = Vec2::new(-30.0, -20.0);
+    let mut world = world(
+        [
+            pivot(),
+            Entity::rectangle(Vec2::new(60.0, 40.0), Motion::new(80.0, 40.0)),
+        ],
+        [Stick::new(0, 1, 20.0).with_anchors(Vec2::ZERO, corner)],
+    );
+
+    for _ in 0..120 {
+        world.update(1.0 / 120.0);
+    }
+
+    let crate_ = world.entities[1].borrow();
+    assert!((length(&world, 0) - 20.0).abs() < 1.0);
+    assert!(crate_.motion.angle.abs() > 0.1, "the crate never turned");
+}
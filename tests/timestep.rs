@@ -0,0 +1,63 @@
+mod common;
+
+use common::positions;
+use simple_physics_engine::{Circle, Entity, FixedTimestep, Motion, Resolver, Vec2, World};
+
+fn world() -> World {
+    World::new(Circle::new(Vec2::ZERO, 300.0), Resolver::default()).with_entities(
+        (0..20).map(|i| Entity::new(10.0, Motion::new(-190.0 + 20.0 * i as f32, -100.0))),
+    )
+}
+
+/// Runs `frames` frames of `frame_time` and then half a step more, so
+/// rounding in the accumulator cannot decide the last step, returning the
+/// steps taken and the world they left.
+fn run(frame_time: f32, frames: usize) -> (usize, World) {
+    let mut world = world();
+    let mut timestep = FixedTimestep::new(1.0 / 60.0, 4, 5);
+    let steps = (0..frames)
+        .map(|_| frame_time)
+        .chain([1.0 / 120.0])
+        .map(|frame_time| timestep.advance(&mut world, frame_time))
+        .sum();
+    (steps, world)
+}
+
+#[test]
+fn frame_rate_does_not_change_the_result() {
+    let (slow_steps, slow) = run(1.0 / 30.0, 60);
+    let (fast_steps, fast) = run(1.0 / 144.0, 288);
+
+    assert_eq!((slow_steps, fast_steps), (120, 120));
+    assert_eq!(positions(&slow), positions(&fast));
+}
+
+#[test]
+fn long_frames_take_at_most_max_steps_and_drop_the_backlog() {
+    let mut world = world();
+    let mut timestep = FixedTimestep::new(0.01, 1, 5);
+
+    assert_eq!(timestep.advance(&mut world, 1.0), 5);
+    assert!(timestep.alpha() < 1.0, "alpha {}", timestep.alpha());
+    // The hitch is forgotten: a normal frame takes a normal step.
+    assert_eq!(timestep.advance(&mut world, 0.01), 1);
+}
+
+#[test]
+fn alpha_is_the_fraction_of_a_step_left_over() {
+    let mut world = world();
+    let mut timestep = FixedTimestep::new(0.01, 1, 5);
+    assert_eq!(timestep.alpha(), 0.0);
+
+    assert_eq!(timestep.advance(&mut world, 0.025), 2);
+    assert!((timestep.alpha() - 0.5).abs() < 1e-3);
+
+    for frame_time in [0.003, 0.05, 0.0199, 0.5, 0.00001] {
+        timestep.advance(&mut world, frame_time);
+        let alpha = timestep.alpha();
+        assert!((0.0..1.0).contains(&alpha), "alpha {}", alpha);
+    }
+
+    timestep.reset();
+    assert_eq!(timestep.alpha(), 0.0);
+}